@@ -0,0 +1,52 @@
+//! Generates the in-process solution registry, see `src/template/registry.rs`.
+//! With the `registry` feature, every `src/bin/NN.rs` and `src/bin/YYYY_NN.rs` is included into the library as a module,
+//! and its `SOLUTION` is listed in `SOLUTIONS`.
+use std::{env, fs, path::Path};
+
+fn main() {
+    // without the `registry` feature the library doesn't include the solutions, so a change to them needn't rerun this.
+    if env::var_os("CARGO_FEATURE_REGISTRY").is_none() {
+        println!("cargo::rerun-if-changed=build.rs");
+        return;
+    }
+
+    let manifest_dir = env::var("CARGO_MANIFEST_DIR").unwrap();
+    let bin_dir = Path::new(&manifest_dir).join("src").join("bin");
+
+    println!("cargo::rerun-if-changed=src/bin");
+
+    // solutions of the home year are named `NN`, those of other years `YYYY_NN`.
+    let is_digits = |s: &str, len: usize| s.len() == len && s.chars().all(|c| c.is_ascii_digit());
+
+    let mut solutions: Vec<(String, String)> = fs::read_dir(&bin_dir)
+        .unwrap()
+        .filter_map(|entry| {
+            let path = entry.ok()?.path();
+            let stem = path.file_stem()?.to_str()?;
+            let is_solution = path.extension()? == "rs"
+                && match stem.split_once('_') {
+                    Some((year, day)) => is_digits(year, 4) && is_digits(day, 2),
+                    None => is_digits(stem, 2),
+                };
+            is_solution.then(|| (stem.to_string(), path.to_str().unwrap().to_string()))
+        })
+        .collect();
+
+    solutions.sort();
+
+    let mut generated =
+        String::from("// @generated by build.rs from the solutions in src/bin.\n\n");
+
+    for (name, path) in &solutions {
+        generated.push_str(&format!("#[path = {path:?}]\nmod day_{name};\n"));
+    }
+
+    generated.push_str("\npub static SOLUTIONS: &[super::Solution] = &[\n");
+    for (name, _) in &solutions {
+        generated.push_str(&format!("    day_{name}::SOLUTION,\n"));
+    }
+    generated.push_str("];\n");
+
+    let out_path = Path::new(&env::var("OUT_DIR").unwrap()).join("solutions.rs");
+    fs::write(out_path, generated).unwrap();
+}
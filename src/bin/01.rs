@@ -1,10 +1,11 @@
 use std::{collections::HashMap, iter::zip};
 
+use advent_of_code::template::{ParseError, parse_lines};
+
 advent_of_code::solution!(1);
 
-pub fn part_one(input: &str) -> Option<u64> {
-    let (mut left_array, mut right_array) =
-        parse_input_to_lists(input).expect("file should be parsible");
+pub fn part_one(input: &str) -> Result<u64, ParseError> {
+    let (mut left_array, mut right_array) = parse_input_to_lists(input)?;
     left_array.sort();
     right_array.sort();
     let mut count = 0;
@@ -15,11 +16,11 @@ pub fn part_one(input: &str) -> Option<u64> {
         };
         count += dif;
     }
-    Some(count)
+    Ok(count)
 }
 
-pub fn part_two(input: &str) -> Option<u64> {
-    let (left_array, right_array) = parse_input_to_lists(input).expect("file should be parsible");
+pub fn part_two(input: &str) -> Result<u64, ParseError> {
+    let (left_array, right_array) = parse_input_to_lists(input)?;
     // first we look through the right array and count each unique location
     let mut similarity_table: HashMap<u64, u64> = HashMap::new();
     right_array.into_iter().for_each(|loc| {
@@ -36,29 +37,23 @@ pub fn part_two(input: &str) -> Option<u64> {
             Some(score) => loc * *score,
             None => 0
         }
-    }).fold(0, |acc, loc_score| acc + loc_score);
-    Some(similarity_score)
+    }).sum::<u64>();
+    Ok(similarity_score)
 }
 
-fn parse_input_to_lists(input: &str) -> Result<(Vec<u64>, Vec<u64>), String> {
-    let lines = input.lines();
-    let no_of_lines = lines.size_hint().0;
-    let id_pairs = lines.map(|l| l.split("   "));
-    let mut left_array: Vec<u64> = Vec::with_capacity(no_of_lines);
-    let mut right_array: Vec<u64> = Vec::with_capacity(no_of_lines);
-    id_pairs.for_each(|mut s| {
-        let left = s.next().expect("should contain first of two entries");
-        let right = s.next().expect("should contain second of two entries");
-        left_array.push(
-            u64::from_str_radix(left, 10)
-                .expect(format!("left part ({}) should be valid int", left).as_str()),
-        );
-        right_array.push(
-            u64::from_str_radix(right, 10)
-                .expect(format!("right part ({}) should be valid int", right).as_str()),
-        );
-    });
-    Ok((left_array, right_array))
+fn parse_input_to_lists(input: &str) -> Result<(Vec<u64>, Vec<u64>), ParseError> {
+    let id_pairs = parse_lines(input, |line| {
+        let (left, right) = line
+            .split_once("   ")
+            .ok_or("expected two entries separated by three spaces")?;
+        let left_id = left.parse::<u64>().map_err(|e| ParseError::from(e).at_column(1))?;
+        // the right entry starts after the left one and the separator.
+        let right_id = right
+            .parse::<u64>()
+            .map_err(|e| ParseError::from(e).offset_by(left.len() + 3))?;
+        Ok::<_, ParseError>((left_id, right_id))
+    })?;
+    Ok(id_pairs.into_iter().unzip())
 }
 
 #[cfg(test)]
@@ -68,12 +63,12 @@ mod tests {
     #[test]
     fn test_part_one() {
         let result = part_one(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(11));
+        assert_eq!(result, Ok(11));
     }
 
     #[test]
     fn test_part_two() {
         let result = part_two(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(31));
+        assert_eq!(result, Ok(31));
     }
 }
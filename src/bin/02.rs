@@ -37,7 +37,7 @@ fn parse_input(input: &str) -> Vec<Vec<u64>> {
     lines
         .map(|l| {
             l.split(' ')
-                .map(|num| u64::from_str_radix(num, 10).unwrap())
+                .map(|num| num.parse::<u64>().unwrap())
                 .collect()
         })
         .collect()
@@ -89,7 +89,7 @@ fn get_direction_from_pair(first: u64, second: u64, direction: Direction) -> Opt
 }
 
 fn is_valid_sequence(seq: &[u64]) -> bool {
-    let mut seq_iter = seq.into_iter().tuple_windows();
+    let mut seq_iter = seq.iter().tuple_windows();
     let first_pair: (&u64, &u64) = match seq_iter.next() {
         Some(pair) => pair,
         None => return false
@@ -109,7 +109,7 @@ fn is_valid_sequence(seq: &[u64]) -> bool {
 }
 
 fn from_seq_return_all_permutations_without_one_element(seq: &[u64]) -> impl Iterator<Item = Vec<u64>> + '_ {
-    (0..seq.len()).into_iter().map(|skip| {
+    (0..seq.len()).map(|skip| {
         let mut vec = Vec::with_capacity(seq.len() - 1);
         vec.extend_from_slice(&seq[..skip]);
         vec.extend_from_slice(&seq[skip + 1..]);
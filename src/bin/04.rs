@@ -1,219 +1,59 @@
+use advent_of_code::shared::{Grid, OrdinalDirection};
+
 advent_of_code::solution!(4);
 
 pub fn part_one(input: &str) -> Option<u64> {
-    let mut output: u64 = 0;
-    let input: Vec<&str> = input.lines().collect();
-    let n = input.len();
-    let input: Vec<char> = input.iter().flat_map(|line| line.chars()).collect();
-
-    // the coords of all the X chars
-    let xs: Vec<Coord> = input
-        .iter()
-        .enumerate()
-        .filter_map(|(raw_ind, &ch)| {
-            if ch == 'X' {
-                Some(Coord::from_raw_ind(raw_ind, n))
-            } else {
-                None
-            }
+    let grid = Grid::from_input(input, |ch| ch);
+
+    let output = grid
+        .grid_point_iter()
+        // words can only start at the X chars
+        .filter(|grid_point| grid_point.point == 'X')
+        .map(|x_point| {
+            // then look out from the X in every direction, including the diagonals
+            OrdinalDirection::ALL
+                .into_iter()
+                .filter(|&dir| {
+                    // and check the next three chars spell out the rest of the word,
+                    // the ray stops at the edge of the grid so short words never match
+                    grid.ray(x_point.loc, dir)
+                        .take(3)
+                        .map(|coord| grid[coord])
+                        .eq(['M', 'A', 'S'])
+                })
+                .count() as u64
         })
-        .collect();
+        .sum();
 
-    [
-        Direction::Up,
-        Direction::UpRight,
-        Direction::Right,
-        Direction::DownRight,
-        Direction::Down,
-        Direction::DownLeft,
-        Direction::Left,
-        Direction::UpLeft,
-    ]
-    .into_iter()
-    .for_each(|dir| {
-        xs.iter().for_each(|x_loc| {
-            x_loc
-                .shift(dir, n)
-                .and_then(|m_loc| {
-                    if let Some('M') = input.get(
-                        m_loc
-                            .to_raw_ind(n)
-                            .expect("shift should rule out invalid coords"),
-                    ) {
-                        return m_loc.shift(dir, n);
-                    }
-                    None
-                })
-                .and_then(|a_loc| {
-                    if let Some('A') = input.get(
-                        a_loc
-                            .to_raw_ind(n)
-                            .expect("shift should rule out invalid coords"),
-                    ) {
-                        return a_loc.shift(dir, n);
-                    }
-                    None
-                })
-                .and_then(|s_loc| {
-                    if let Some('S') = input.get(
-                        s_loc
-                            .to_raw_ind(n)
-                            .expect("shift should rule out invalid coords"),
-                    ) {
-                        return Some(s_loc);
-                    }
-                    None
-                })
-                .map(|_| output += 1);
-        });
-    });
     Some(output)
 }
 
-#[derive(Debug, Clone, Copy)]
-struct Coord {
-    row: usize,
-    col: usize,
-}
-
-impl Coord {
-    fn to_raw_ind(&self, n: usize) -> Option<usize> {
-        if self.row >= n || self.col >= n {
-            return None;
-        }
-        Some(self.row * n + self.col)
-    }
-
-    fn from_raw_ind(raw_ind: usize, n: usize) -> Self {
-        Self {
-            row: raw_ind / n,
-            col: raw_ind % n,
-        }
-    }
-
-    fn shift_up(self, _n: usize) -> Option<Self> {
-        if self.row > 0 {
-            return Some(Self {
-                row: self.row - 1,
-                col: self.col,
-            });
-        }
-        None
-    }
-
-    fn shift_right(self, n: usize) -> Option<Self> {
-        if self.col < n - 1 {
-            return Some(Self {
-                row: self.row,
-                col: self.col + 1,
-            });
-        }
-        None
-    }
-
-    fn shift_down(self, n: usize) -> Option<Self> {
-        if self.row < n - 1 {
-            return Some(Self {
-                row: self.row + 1,
-                col: self.col,
-            });
-        }
-        None
-    }
-
-    fn shift_left(self, _n: usize) -> Option<Self> {
-        if self.col > 0 {
-            return Some(Self {
-                row: self.row,
-                col: self.col - 1,
-            });
-        }
-        None
-    }
-
-    fn shift(self, direction: Direction, n: usize) -> Option<Self> {
-        use Direction::*;
-        match direction {
-            Up => self.shift_up(n),
-            UpRight => self.shift_up(n).and_then(|c| c.shift_right(n)),
-            Right => self.shift_right(n),
-            DownRight => self.shift_down(n).and_then(|c| c.shift_right(n)),
-            Down => self.shift_down(n),
-            DownLeft => self.shift_down(n).and_then(|c| c.shift_left(n)),
-            Left => self.shift_left(n),
-            UpLeft => self.shift_up(n).and_then(|c| c.shift_left(n)),
-        }
-    }
-}
-
-#[derive(Debug, Clone, Copy)]
-enum Direction {
-    Up,
-    UpRight,
-    Right,
-    DownRight,
-    Down,
-    DownLeft,
-    Left,
-    UpLeft,
-}
-
 pub fn part_two(input: &str) -> Option<u64> {
-    // initialise some stuff
-    let mut output: u64 = 0;
-    let input: Vec<&str> = input.lines().collect();
-    let n = input.len();
-    let input: Vec<char> = input.iter().flat_map(|line| line.chars()).collect();
-
-    // this time we find the 'A' chars
-    let a_chars: Vec<Coord> = input
-        .iter()
-        .enumerate()
-        .filter_map(|(raw_ind, &ch)| {
-            if ch == 'A' {
-                Some(Coord::from_raw_ind(raw_ind, n))
-            } else {
-                None
-            }
+    let grid = Grid::from_input(input, |ch| ch);
+
+    // this time we find the 'A' chars, since they sit in the middle of the cross
+    let output = grid
+        .grid_point_iter()
+        .filter(|grid_point| grid_point.point == 'A')
+        .filter(|a_point| {
+            // now we need to check around the 'A' chars, in the diagonals, and look for a valid pattern
+            let neighbors = grid.get_all_neighbors_from_coord(a_point.loc);
+            let diags: Vec<Option<char>> = OrdinalDirection::DIAGONALS
+                .into_iter()
+                // neighbours off the edge of the grid are None
+                .map(|dir| neighbors.get(dir).map(|(_, &ch)| ch))
+                .collect();
+            matches!(
+                diags.as_slice(),
+                [Some('M'), Some('M'), Some('S'), Some('S')]
+                    | [Some('S'), Some('M'), Some('M'), Some('S')]
+                    | [Some('S'), Some('S'), Some('M'), Some('M')]
+                    | [Some('M'), Some('S'), Some('S'), Some('M')]
+            )
         })
-        .collect();
+        .count();
 
-    // now we need to check around the 'A' chars, shifting in the diagonals, and look for a valid pattern
-    a_chars.into_iter().for_each(|coord| {
-        let diags: Vec<Option<char>> = [
-            Direction::UpRight,
-            Direction::DownRight,
-            Direction::DownLeft,
-            Direction::UpLeft,
-        ]
-        .into_iter()
-        .map(|dir| {
-            // check we can shift in that direction
-            if let Some(diag) = coord.clone().shift(dir, n) {
-                // check that there is a char there in the input
-                if let Some(&ch) = input.get(
-                    diag.to_raw_ind(n)
-                        .expect("shift should rule out invalid coords"),
-                ) {
-                    // only return the chars we care about
-                    if ch == 'M' || ch == 'S' {
-                        return Some(ch);
-                    }
-                }
-            }
-            // otherwise return None
-            None
-        })
-        .collect();
-        match diags.as_slice() {
-            [Some('M'), Some('M'), Some('S'), Some('S')] => output += 1,
-            [Some('S'), Some('M'), Some('M'), Some('S')] => output += 1,
-            [Some('S'), Some('S'), Some('M'), Some('M')] => output += 1,
-            [Some('M'), Some('S'), Some('S'), Some('M')] => output += 1,
-            _ => {}
-        }
-    });
-    Some(output)
+    Some(output as u64)
 }
 
 #[cfg(test)]
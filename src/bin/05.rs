@@ -26,7 +26,7 @@ pub fn part_one(input: &str) -> Option<u64> {
     )
 }
 
-fn is_valid_page_order(page_set: &Vec<u64>, rules_map: &HashMap<u64, HashSet<u64>>) -> bool {
+fn is_valid_page_order(page_set: &[u64], rules_map: &HashMap<u64, HashSet<u64>>) -> bool {
     (1..page_set.len()).fold(true, |valid, ind| {
         // if we have already invalidated, return early
         if !valid {
@@ -89,7 +89,7 @@ pub fn part_two(input: &str) -> Option<u64> {
 fn parse_input(input: &str) -> (HashMap<u64, HashSet<u64>>, Vec<Vec<u64>>) {
     let mut rule_map: HashMap<u64, HashSet<u64>> = HashMap::new();
     let mut pages = Vec::new();
-    input.lines().into_iter().for_each(|line| {
+    input.lines().for_each(|line| {
         let v_line: Vec<char> = line.chars().collect();
         match v_line.get(2) {
             // a rule
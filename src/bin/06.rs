@@ -1,60 +1,34 @@
 use std::collections::HashMap;
 
-use itertools::Itertools;
+use advent_of_code::shared::{Canvas, CardinalDirection as Direction, Color, Coord, Grid};
+use advent_of_code::template::visualize;
 
 advent_of_code::solution!(6);
 
 pub fn part_one(input: &str) -> Option<u64> {
-    let debug = false;
-    let (lab_width, lab_hieght) = get_lab_dimensions(input);
-
-    let (lab, guard) = parse_input(input, &lab_width, &lab_hieght);
-    let all_visited_locs = get_finite_path_information(guard, &lab, debug);
+    let (lab, guard) = parse_input(input);
+    let all_visited_locs = get_finite_path_information(guard, &lab);
     Some(all_visited_locs.len() as u64)
 }
 
-fn parse_input<'a>(
-    input: &str,
-    lab_width: &'a usize,
-    lab_height: &'a usize,
-) -> (LabGrid<'a>, Guard<'a>) {
-    // use helper function to generate our lab
-    let lab = build_lab_grid_from_str(input, &lab_width, &lab_height);
+fn parse_input(input: &str) -> (LabGrid, Guard) {
+    let floor_plan = Grid::from_input(input, |ch| ch);
     // find the guard in the lab, and then initialise a Guard instance
-    let (guard_starting_position, _) = input
-        .lines()
-        .flat_map(|line| line.chars())
-        .enumerate()
-        .find(|(_, ch)| *ch == '^')
+    let guard_starting_position = floor_plan
+        .position(|&ch| ch == '^')
         .expect("valid input must have a guard");
-    let guard = Guard::new(Coord::from_raw_ind(
-        guard_starting_position,
-        &lab_width,
-        &lab_height,
-    ));
-    (lab, guard)
-}
-
-fn get_lab_dimensions(input: &str) -> (usize, usize) {
-    // collect our static grid size references
-    let lab_height = input.lines().count();
-    let lab_width = input
-        .lines()
-        .next()
-        .expect("input should not be empty")
-        .chars()
-        .count();
-    (lab_height, lab_width)
+    let guard = Guard::new(guard_starting_position);
+    // use helper function to generate our lab
+    (build_lab_grid(floor_plan), guard)
 }
 
 fn get_finite_path_information(
-    guard: Guard<'_>,
-    lab: &LabGrid<'_>,
-    debug: bool,
+    guard: Guard,
+    lab: &LabGrid,
 ) -> HashMap<(usize, usize), LocationHistory> {
     // initialise next_obstacle
-    let mut next_obstacle = get_next_obst(&lab, &guard);
-    let mut guard: Result<Guard<'_>, HashMap<(usize, usize), LocationHistory>> = Ok(guard);
+    let mut next_obstacle = get_next_obst(lab, &guard);
+    let mut guard: Result<Guard, HashMap<(usize, usize), LocationHistory>> = Ok(guard);
     loop {
         guard = match guard {
             Err(e) => {
@@ -67,18 +41,12 @@ fn get_finite_path_information(
                 let mut path_clear = false;
                 while !path_clear {
                     if let Some(obst) = next_obstacle {
-                        if inner_guard.is_blocked_by(obst) {
-                            if debug {
-                                println!("guard has hit obstacle");
-                                display_grid(
-                                    &inner_guard,
-                                    &lab,
-                                    inner_guard.location.col_height,
-                                    inner_guard.location.row_width,
-                                );
-                            }
+                        if inner_guard.is_blocked_by(obst, lab) {
+                            visualize::frame("guard has hit an obstacle", || {
+                                draw_lab(&inner_guard, lab)
+                            });
                             inner_guard.rotate();
-                            next_obstacle = get_next_obst(&lab, &inner_guard);
+                            next_obstacle = get_next_obst(lab, &inner_guard);
                         } else {
                             // path not yet blocked, still some distant object
                             path_clear = true;
@@ -89,7 +57,7 @@ fn get_finite_path_information(
                     }
                 }
                 // then we march forwards
-                inner_guard.move_one_step()
+                inner_guard.move_one_step(lab)
             }
         };
 
@@ -100,66 +68,23 @@ fn get_finite_path_information(
     guard.expect_err("only breaks from loop if err")
 }
 
-fn display_grid(guard: &Guard, lab: &LabGrid, lab_height: &usize, lab_width: &usize) {
-    // initialise grid
-    let mut grid = vec!['.'; lab_height * lab_width];
-    // populate with obstacles
-    lab.layout.iter().for_each(|obst_coord| {
-        grid[obst_coord.to_raw_ind()] = '#';
-    });
-    // show all the places the guard has been
-    guard.visited.iter().for_each(|((row, col), _)| {
-        let raw_ind = row * lab_width + col;
-        match grid.get(raw_ind) {
-            Some('#') => {
-                panic!("Guard has collided with an obstacle at ({}, {})", row, col);
-            }
-            Some('.') => grid[raw_ind] = 'X',
-            None => {
-                panic!(
-                    "Guard visited a position out of bounds at ({}, {})",
-                    row, col
-                );
-            }
-            _ => {}
-        }
-    });
-    // display the guard themselves
+/// Draws the lab with the guard's path so far and the guard themselves, for `--visualize`
+fn draw_lab(guard: &Guard, lab: &LabGrid) -> Canvas {
+    let visited = guard.visited.keys().map(|&loc| Coord::from(loc));
     let guard_char = match guard.direction {
-        Direction::Left => 'L',
-        Direction::Right => 'R',
-        Direction::Up => 'U',
-        Direction::Down => 'D',
+        Direction::Left => '<',
+        Direction::Right => '>',
+        Direction::Up => '^',
+        Direction::Down => 'v',
     };
-    let guard_raw_ind = guard.location.to_raw_ind();
-    let (guard_row, guard_col) = (guard.location.row, guard.location.col);
-    match grid.get(guard_raw_ind) {
-        Some('.') | Some('X') => grid[guard_raw_ind] = guard_char,
-        Some('#') => {
-            panic!(
-                "Guard has collided with an obstacle at ({}, {})",
-                guard_row, guard_col
-            );
-        }
-        None => {
-            panic!(
-                "Guard visited a position out of bounds at ({}, {})",
-                guard_row, guard_col
-            );
-        }
-        _ => {}
-    }
-    // finally display the grid
-    (0..*lab_height).for_each(|row_ind| {
-        let row = (row_ind * *lab_width..(row_ind + 1) * *lab_width)
-            .map(|raw_ind| grid[raw_ind])
-            .join(" ");
-        println!("{}", row)
-    });
-    println!("")
+    // the layout also holds any obstacle we inserted, which is not on the floor plan
+    Canvas::from_grid(&lab.floor_plan, |_| '.')
+        .path(lab.layout.iter().copied(), '#', Color::Blue)
+        .path(visited, 'X', Color::Yellow)
+        .mark(guard.location, guard_char, Color::Red)
 }
 
-fn get_next_obst<'a>(lab: &'a LabGrid, guard: &Guard) -> Option<&'a Coord<'a>> {
+fn get_next_obst<'a>(lab: &'a LabGrid, guard: &Guard) -> Option<&'a Coord> {
     use Direction::*;
     let (view_dir, ind) = guard.get_view();
     let (curr_row, curr_col) = guard.get_pos();
@@ -206,11 +131,10 @@ fn get_next_obst<'a>(lab: &'a LabGrid, guard: &Guard) -> Option<&'a Coord<'a>> {
 
 pub fn part_two(input: &str) -> Option<u64> {
     // TODO: make this more performant
-    let (lab_width, lab_height) = get_lab_dimensions(input);
-    let (mut original_lab, original_guard) = parse_input(input, &lab_width, &lab_height);
-    let original_guard_loc: (usize, usize) = original_guard.location.clone().into();
+    let (mut original_lab, original_guard) = parse_input(input);
+    let original_guard_loc: (usize, usize) = original_guard.location.into();
     let potential_object_locations: Vec<(usize, usize)> =
-        get_finite_path_information(original_guard.clone(), &original_lab, false)
+        get_finite_path_information(original_guard.clone(), &original_lab)
             .into_keys()
             // filter out the guard's starting location, since we cannot put an object there
             // due to paradoxes
@@ -221,12 +145,7 @@ pub fn part_two(input: &str) -> Option<u64> {
         .filter(|loc| {
             // create a new guard for this timeline
             let guard = original_guard.clone();
-            let new_obst = Coord {
-                row: loc.0,
-                col: loc.1,
-                row_width: &lab_width,
-                col_height: &lab_height,
-            };
+            let new_obst = Coord::from(*loc);
             // now we need to calculate the path, returning true if we detect a loop
             // and false if the guard exits the lab before a loop is detected
             with_inserted_obstacle(&mut original_lab, new_obst, |lab| {
@@ -237,117 +156,15 @@ pub fn part_two(input: &str) -> Option<u64> {
     Some(object_locations as u64)
 }
 
-#[derive(Debug, Clone, Copy, PartialEq)]
-struct Coord<'a> {
-    row: usize,
-    col: usize,
-    row_width: &'a usize,
-    col_height: &'a usize,
-}
-
-impl<'a> Coord<'a> {
-    fn shift_left(self) -> Option<Self> {
-        if self.col > 0 {
-            Some(Self {
-                row: self.row,
-                col: self.col - 1,
-                row_width: self.row_width,
-                col_height: self.col_height,
-            })
-        } else {
-            None
-        }
-    }
-
-    fn shift_right(self) -> Option<Self> {
-        if self.col < self.row_width - 1 {
-            Some(Self {
-                row: self.row,
-                col: self.col + 1,
-                row_width: self.row_width,
-                col_height: self.col_height,
-            })
-        } else {
-            None
-        }
-    }
-
-    fn shift_up(self) -> Option<Self> {
-        if self.row > 0 {
-            Some(Self {
-                row: self.row - 1,
-                col: self.col,
-                row_width: self.row_width,
-                col_height: self.col_height,
-            })
-        } else {
-            None
-        }
-    }
-
-    fn shift_down(self) -> Option<Self> {
-        if self.row < self.col_height - 1 {
-            Some(Self {
-                row: self.row + 1,
-                col: self.col,
-                row_width: self.row_width,
-                col_height: self.col_height,
-            })
-        } else {
-            None
-        }
-    }
-
-    /// Shifts the coordinate 1 step in the given direction.
-    /// Returns an option where None represents leaving the bounds of the grid.
-    pub fn shift(self, dir: Direction) -> Option<Self> {
-        use Direction::*;
-        match dir {
-            Left => self.shift_left(),
-            Right => self.shift_right(),
-            Up => self.shift_up(),
-            Down => self.shift_down(),
-        }
-    }
-
-    pub fn from_raw_ind(raw_ind: usize, grid_width: &'a usize, grid_height: &'a usize) -> Self {
-        Self {
-            row: raw_ind / *grid_width,
-            col: raw_ind % *grid_width,
-            row_width: grid_width,
-            col_height: grid_height,
-        }
-    }
-
-    pub fn to_raw_ind(&self) -> usize {
-        let row_len = self.row_width.clone();
-        self.row * row_len + self.col
-    }
-}
-
-#[derive(Debug, Clone, Copy, PartialEq)]
-enum Direction {
-    Left,
-    Right,
-    Up,
-    Down,
-}
-
-impl From<Coord<'_>> for (usize, usize) {
-    fn from(value: Coord<'_>) -> Self {
-        (value.row, value.col)
-    }
-}
-
 #[derive(Debug, Clone)]
-struct Guard<'a> {
-    location: Coord<'a>,
+struct Guard {
+    location: Coord,
     direction: Direction,
     visited: HashMap<(usize, usize), LocationHistory>,
 }
 
-impl<'a> Guard<'a> {
-    pub fn new(starting_location: Coord<'a>) -> Self {
+impl Guard {
+    pub fn new(starting_location: Coord) -> Self {
         let mut visited = HashMap::new();
         visited.increment_visit_count(starting_location.into());
         Self {
@@ -359,8 +176,11 @@ impl<'a> Guard<'a> {
 
     /// Naively moves one position forwards. Returns Ok(self) unless it has exited the grid,
     /// in which case it returns Err(positions) for further analysis.
-    pub fn move_one_step(mut self) -> Result<Self, HashMap<(usize, usize), LocationHistory>> {
-        let new_location = match self.location.shift(self.direction) {
+    pub fn move_one_step(
+        mut self,
+        lab: &LabGrid,
+    ) -> Result<Self, HashMap<(usize, usize), LocationHistory>> {
+        let new_location = match lab.floor_plan.shift(self.location, self.direction) {
             Some(x) => x,
             None => return Err(self.visited),
         };
@@ -399,19 +219,18 @@ impl<'a> Guard<'a> {
         self.location.into()
     }
 
-    pub fn is_blocked_by(&self, obst: &Coord<'a>) -> bool {
-        if let Some(new_pos) = self.location.clone().shift(self.direction) {
-            if &new_pos == obst {
-                return true;
-            }
-        }
-        false
+    pub fn is_blocked_by(&self, obst: &Coord, lab: &LabGrid) -> bool {
+        lab.floor_plan
+            .shift(self.location, self.direction)
+            .is_some_and(|new_pos| &new_pos == obst)
     }
 }
 
 #[derive(Debug)]
-struct LabGrid<'a> {
-    layout: Vec<Coord<'a>>,
+struct LabGrid {
+    /// The lab as it was read from the input, which also gives us the bounds for the guard
+    floor_plan: Grid<char>,
+    layout: Vec<Coord>,
     row_look_up: HashMap<usize, Vec<usize>>,
     col_look_up: HashMap<usize, Vec<usize>>,
 }
@@ -478,23 +297,11 @@ impl IncrementLocationHistory for HashMap<(usize, usize), LocationHistory> {
     }
 }
 
-fn build_lab_grid_from_str<'a>(
-    input: &str,
-    row_width: &'a usize,
-    col_height: &'a usize,
-) -> LabGrid<'a> {
-    let rows: Vec<&str> = input.lines().collect();
-    let layout: Vec<Coord> = rows
-        .into_iter()
-        .flat_map(|row| row.chars())
-        .enumerate()
-        .filter_map(|(ind, ch)| {
-            if ch == '#' {
-                Some(Coord::from_raw_ind(ind, row_width, col_height))
-            } else {
-                None
-            }
-        })
+fn build_lab_grid(floor_plan: Grid<char>) -> LabGrid {
+    let layout: Vec<Coord> = floor_plan
+        .grid_point_iter()
+        .filter(|grid_point| grid_point.point == '#')
+        .map(|grid_point| grid_point.loc)
         .collect();
 
     let mut row_look_up: HashMap<usize, Vec<usize>> = HashMap::new();
@@ -509,32 +316,24 @@ fn build_lab_grid_from_str<'a>(
     });
 
     LabGrid {
+        floor_plan,
         layout,
         row_look_up,
         col_look_up,
     }
 }
 
-fn check_for_path_loop(mut guard: Guard<'_>, lab: &LabGrid<'_>) -> bool {
+fn check_for_path_loop(mut guard: Guard, lab: &LabGrid) -> bool {
     // initialise next_obstacle
-    let mut next_obstacle = get_next_obst(&lab, &guard);
+    let mut next_obstacle = get_next_obst(lab, &guard);
     loop {
         let mut path_blocked = true;
 
         while path_blocked {
             if let Some(obst) = next_obstacle {
-                if guard.is_blocked_by(obst) {
-                    // println!("guard has hit obstacle {:?}", obst);
-                    // display_grid(
-                    //    &guard,
-                    //     &lab,
-                    //     guard.location.col_height,
-                    //     guard.location.row_width,
-                    // );
+                if guard.is_blocked_by(obst, lab) {
                     guard.rotate();
-                    // println!("Rotated! Now facing {:?}", guard.direction);
                     next_obstacle = get_next_obst(lab, &guard);
-                    // println!("calculated {:?} as next obstacle", next_obstacle);
                 } else {
                     path_blocked = false;
                 }
@@ -543,19 +342,10 @@ fn check_for_path_loop(mut guard: Guard<'_>, lab: &LabGrid<'_>) -> bool {
             }
         }
 
-        guard = match guard.move_one_step() {
-            Ok(next_loc) => {
-                // println!(
-                //     "Guard has moved to ({}, {})",
-                //     next_loc.location.row, next_loc.location.col
-                // );
-                next_loc
-            }
+        guard = match guard.move_one_step(lab) {
+            Ok(next_loc) => next_loc,
             // the guard has left the valid bounds before a loop has been detected
-            Err(_) => {
-                println!("Guard has left the area!");
-                return false;
-            }
+            Err(_) => return false,
         };
 
         // now we check for a loop at the new location
@@ -566,14 +356,14 @@ fn check_for_path_loop(mut guard: Guard<'_>, lab: &LabGrid<'_>) -> bool {
         // cache this
         let current_loc = guard.location.into();
         // check to see if we visited this location before
-        if let Some(loc) = guard.visited.get(&current_loc) {
-            if loc.check_for_direction(guard.direction) {
-                // if we have been to this location before, and faced that direction,
-                // then our next steps must be the same, because the guard is deterministic
-                // therefore we are in a loop
-                println!("Loop detected!");
-                return true;
-            }
+        if let Some(loc) = guard.visited.get(&current_loc)
+            && loc.check_for_direction(guard.direction)
+        {
+            // if we have been to this location before, and faced that direction,
+            // then our next steps must be the same, because the guard is deterministic
+            // therefore we are in a loop
+            visualize::frame("guard is stuck in a loop", || draw_lab(&guard, lab));
+            return true;
         }
 
         // update the last visit direction for future visits
@@ -586,7 +376,7 @@ fn check_for_path_loop(mut guard: Guard<'_>, lab: &LabGrid<'_>) -> bool {
 /// A helper function to run some process on a lab layout with a temporary new obstacle.
 /// Inserts the obstacle, runs the process, and then resets the obstacle, before returning
 /// the result of the process.
-fn with_inserted_obstacle<'a, F, R>(lab: &mut LabGrid<'a>, new_obst: Coord<'a>, f: F) -> R
+fn with_inserted_obstacle<F, R>(lab: &mut LabGrid, new_obst: Coord, f: F) -> R
 where
     F: FnOnce(&LabGrid) -> R,
 {
@@ -634,22 +424,16 @@ mod tests {
     fn returns_true_when_finding_a_loop() {
         // arrange
         let input = advent_of_code::template::read_file("examples", DAY);
-        let (w, h) = get_lab_dimensions(&input);
-
-        let (mut lab, guard) = parse_input(&input, &w, &h);
-        let (lab_copy, _) = parse_input(&input, &w, &h);
-        let new_obst = Coord {
-            row: 7,
-            col: 6,
-            row_width: &w,
-            col_height: &h,
-        };
+
+        let (mut lab, guard) = parse_input(&input);
+        let (lab_copy, _) = parse_input(&input);
+        let new_obst = Coord::new(7, 6);
 
         // act
-        let output = with_inserted_obstacle(&mut lab, new_obst, |l| check_for_path_loop(guard, &l));
+        let output = with_inserted_obstacle(&mut lab, new_obst, |l| check_for_path_loop(guard, l));
 
         // assert
-        assert_eq!(output, true);
+        assert!(output);
         assert_eq!(lab.layout, lab_copy.layout);
         assert_eq!(lab.row_look_up, lab_copy.row_look_up);
         assert_eq!(lab.col_look_up, lab_copy.col_look_up);
@@ -659,13 +443,12 @@ mod tests {
     fn returns_false_when_no_loop_found() {
         // arrange
         let input = advent_of_code::template::read_file("examples", DAY);
-        let (w, h) = get_lab_dimensions(&input);
-        let (lab, guard) = parse_input(&input, &w, &h);
+        let (lab, guard) = parse_input(&input);
 
         // act
         let output = check_for_path_loop(guard, &lab);
 
         // assert
-        assert_eq!(output, false)
+        assert!(!output)
     }
 }
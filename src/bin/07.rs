@@ -1,35 +1,19 @@
 use advent_of_code::shared::{match_numeric, parse_number_from_str};
+use advent_of_code::template::{ParseError, parse_lines};
 
 advent_of_code::solution!(7);
 
-pub fn part_one(input: &str) -> Option<u64> {
-    Some(
-        input
-            .lines()
-            .map(|line| parse_line(line).and_then(|case| Ok(evaluate_test_case(case))))
-            .fold(0, |acc, next_res| {
-                if let Ok(Some(num)) = next_res {
-                    return acc + num;
-                }
-                acc
-            }),
-    )
+pub fn part_one(input: &str) -> Result<u64, ParseError> {
+    let cases = parse_lines(input, parse_line)?;
+    Ok(cases.into_iter().filter_map(evaluate_test_case).sum())
 }
 
-pub fn part_two(input: &str) -> Option<u64> {
-    Some(
-        input
-            .lines()
-            .map(|line| {
-                parse_line(line).and_then(|case| Ok(evaluate_test_case_with_concatenation(case)))
-            })
-            .fold(0, |acc, next_res| {
-                if let Ok(Some(num)) = next_res {
-                    return acc + num;
-                }
-                acc
-            }),
-    )
+pub fn part_two(input: &str) -> Result<u64, ParseError> {
+    let cases = parse_lines(input, parse_line)?;
+    Ok(cases
+        .into_iter()
+        .filter_map(evaluate_test_case_with_concatenation)
+        .sum())
 }
 struct TestCase {
     target: u64,
@@ -72,7 +56,7 @@ fn evaluate_test_case(case: TestCase) -> Option<u64> {
 /// otherwise `None`
 fn evaluate_test_case_with_concatenation(case: TestCase) -> Option<u64> {
     fn inner_rsolve(target: u64, current: u64, next: &[u64]) -> bool {
-        if next.len() == 0 {
+        if next.is_empty() {
             return current == target;
         }
         let possible_currents = [
@@ -109,21 +93,23 @@ impl Concatenation for u64 {
     }
 }
 
-fn parse_line(line: &str) -> Result<TestCase, String> {
-    let parts: Vec<&str> = line.split(": ").collect();
-
-    let target: u64 = match parts.get(0) {
-        Some(&target) => parse_number_from_str(target)?,
-        None => return Err(format!("Invalid line format: {}", line)),
+fn parse_line(line: &str) -> Result<TestCase, ParseError> {
+    let Some((target, inputs)) = line.split_once(": ") else {
+        return Err(ParseError::new(format!("Invalid line format: {}", line)));
     };
 
-    let inputs = match parts.get(1) {
-        Some(&inputs) => inputs
-            .split(" ")
-            .map(|str| parse_number_from_str(str))
-            .collect::<Result<Vec<u64>, String>>()?,
-        None => return Err(format!("Invalid line format: {}", line)),
-    };
+    // inputs start after the target and the `: ` separator.
+    let mut offset = target.len() + 2;
+    let target = parse_number_from_str(target)?;
+
+    let inputs = inputs
+        .split(" ")
+        .map(|input| {
+            let num = parse_number_from_str(input).map_err(|e| e.offset_by(offset));
+            offset += input.len() + 1;
+            num
+        })
+        .collect::<Result<Vec<u64>, ParseError>>()?;
     Ok(TestCase { target, inputs })
 }
 
@@ -134,12 +120,12 @@ mod tests {
     #[test]
     fn test_part_one() {
         let result = part_one(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(3_749));
+        assert_eq!(result, Ok(3_749));
     }
 
     #[test]
     fn test_part_two() {
         let result = part_two(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(11_387));
+        assert_eq!(result, Ok(11_387));
     }
 }
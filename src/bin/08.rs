@@ -199,12 +199,12 @@ fn calculate_antinode_harmonics(
     second_antenna: &Coord,
     bounds: &GridBounds,
 ) -> Vec<Coord> {
-    let mut output: Vec<Coord> = vec![first_antenna.clone(), second_antenna.clone()];
+    let mut output: Vec<Coord> = vec![*first_antenna, *second_antenna];
     let mut dist = Distance::from_coords(first_antenna, second_antenna);
     // start by going from second onwards
     let mut next_node = second_antenna.add_distance(&dist, bounds);
     while let Some(node) = next_node {
-        output.push(node.clone());
+        output.push(node);
         next_node = output
             .last()
             .expect("just pushed, must exist")
@@ -214,7 +214,7 @@ fn calculate_antinode_harmonics(
     dist.flip();
     next_node = first_antenna.add_distance(&dist, bounds);
     while let Some(node) = next_node {
-        output.push(node.clone());
+        output.push(node);
         next_node = output
             .last()
             .expect("just pushed, must exist")
@@ -1,7 +1,7 @@
-use advent_of_code::shared::match_numeric;
+use advent_of_code::shared::{Canvas, Color, Coord, match_numeric};
+use advent_of_code::template::visualize;
 
 advent_of_code::solution!(9);
-const DEBUG: bool = false;
 
 pub fn part_one(input: &str) -> Option<u64> {
     let drive_blocks = parse_input_to_disk(input);
@@ -11,7 +11,7 @@ pub fn part_one(input: &str) -> Option<u64> {
         .iter()
         .enumerate()
         .filter_map(|(block_ind, block_contents)| {
-            if let Some(_) = block_contents {
+            if block_contents.is_some() {
                 Some(block_ind)
             } else {
                 None
@@ -32,31 +32,31 @@ pub fn part_one(input: &str) -> Option<u64> {
         }
     }
 
-    if DEBUG {
-        println!(
-            "{}",
-            new_arrangement
-                .iter()
-                .map(|num| num.to_string())
-                .collect::<String>()
-        );
-    }
-
-    let final_drive: Vec<Option<u64>> = new_arrangement.into_iter().map(|id| Some(id)).collect();
+    let final_drive: Vec<Option<u64>> = new_arrangement.into_iter().map(Some).collect();
+    visualize::frame("compacted disk", || draw_disk(&final_drive));
 
     let checksum = calculate_checksum(final_drive);
     Some(checksum)
 }
 
+/// Draws the disk with the last digit of each file id, coloured so neighbouring files stand apart
+fn draw_disk(disk: &[Option<u64>]) -> Canvas {
+    let canvas = Canvas::from_strip(disk, |_| '.');
+    disk.iter().enumerate().fold(canvas, |canvas, (ind, block)| match block {
+        Some(id) => {
+            let digit = char::from_digit((id % 10) as u32, 10).expect("always a single digit");
+            canvas.mark(Coord::new(0, ind), digit, Color::ALL[*id as usize % Color::ALL.len()])
+        }
+        None => canvas,
+    })
+}
+
 fn calculate_checksum(file_blocks: Vec<Option<u64>>) -> u64 {
     file_blocks
         .into_iter()
         .enumerate()
         .fold(0u64, |acc, (block_ind, block_contents)| {
-            let block_value = match block_contents {
-                Some(id) => id,
-                None => 0
-            };
+            let block_value = block_contents.unwrap_or_default();
             acc + (block_value * block_ind as u64)
         })
 }
@@ -88,20 +88,11 @@ pub fn part_two(input: &str) -> Option<u64> {
             (file.start_index .. file.start_index + file.length).for_each(|ind| {
                 disk[ind] = None
             });
+            visualize::frame(format_args!("moved file {}", file.file_id), || draw_disk(&disk));
         }
         // if there is no free space to fit the file, we just move on to the next file
     });
 
-    if DEBUG {
-        let for_display: String = disk.iter().map(|mem| {
-            match mem {
-                Some(id) => id.to_string(),
-                None => ".".to_string(),
-            }
-        }).collect();
-        print!("{}", for_display)
-    }
-
     // now we have our disk rearranged, we can calculate the checksum and return it as our output
     let output = calculate_checksum(disk);
     Some(output)
@@ -229,8 +220,8 @@ fn parse_input_to_file_blocks(input: &str) -> Vec<FileBlock> {
                 // calculate our FileInfo
                 let output = FileBlock::File(
                     FileBlockInfo { 
-                        file_id: file_id_counter.clone(),
-                        start_index: index_counter.clone(),
+                        file_id: file_id_counter,
+                        start_index: index_counter,
                         length: block_length as usize
                     }
                 );
@@ -1,18 +1,31 @@
 use std::collections::HashMap;
 
-advent_of_code::solution!(11);
+advent_of_code::solution!(11, params: Params);
 
-pub fn part_one(input: &str) -> Option<u64> {
+advent_of_code::params! {
+    pub struct Params {
+        blinks_part_one: u64 = 25,
+        blinks_part_two: u64 = 75,
+    }
+}
+
+pub fn part_one(input: &str, params: &Params) -> Option<u64> {
     let starting_stones = parse_input_to_stones(input);
     let mut mem_dict = MemDict::new();
-    let result = starting_stones.into_iter().map(|stone| get_stone_state(stone, 25, &mut mem_dict)).sum();
+    let result = starting_stones
+        .into_iter()
+        .map(|stone| get_stone_state(stone, params.blinks_part_one, &mut mem_dict))
+        .sum();
     Some(result)
 }
 
-pub fn part_two(input: &str) -> Option<u64> {
+pub fn part_two(input: &str, params: &Params) -> Option<u64> {
     let starting_stones = parse_input_to_stones(input);
     let mut mem_dict = MemDict::new();
-    let result = starting_stones.into_iter().map(|stone| get_stone_state(stone, 75, &mut mem_dict)).sum();
+    let result = starting_stones
+        .into_iter()
+        .map(|stone| get_stone_state(stone, params.blinks_part_two, &mut mem_dict))
+        .sum();
     Some(result)
 }
 
@@ -57,11 +70,11 @@ impl Stone {
         // second rule, split in half by char
         let num_string = self.num.to_string();
         let num_len = num_string.len();
-        if num_len % 2 == 0 {
+        if num_len.is_multiple_of(2) {
             let half_len = num_len / 2;
-            let first_num = (&num_string[0..half_len]).parse()
+            let first_num = num_string[0..half_len].parse()
                 .expect("valid input should create splittable numbers");
-            let second_num = (&num_string[half_len..]).parse()
+            let second_num = num_string[half_len..].parse()
                 .expect("valid input should create splittable numbers");
             return StoneOutput::Split(Stone { num: first_num }, Stone { num: second_num });
         }
@@ -78,15 +91,14 @@ enum StoneOutput {
 /// Takes in the input format and returns a Vec of Stones.
 /// For example `0 1 2` would return `vec![ Stone { num: 0 }, Stone { num: 1 }, Stone { num: 2 } ]`
 fn parse_input_to_stones(input: &str) -> Vec<Stone> {
-    let row = input.split(" ")
+    input.split(" ")
         .map(|num_str| {
             match num_str.parse() {
                 Ok(num) => Stone { num },
                 Err(e) => panic!("Error in StoneRow::from_str(): {}", e),
             }
         })
-        .collect();
-    row
+        .collect()
 }
 
 #[cfg(test)]
@@ -95,14 +107,21 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_one(&advent_of_code::template::read_file("examples", DAY), &Params::default());
         assert_eq!(result, Some(55_312));
     }
 
+    #[test]
+    fn test_part_one_six_blinks() {
+        let params = Params { blinks_part_one: 6, ..Params::default() };
+        let result = part_one(&advent_of_code::template::read_file("examples", DAY), &params);
+        assert_eq!(result, Some(22));
+    }
+
     // test two is invalid since there is no given answer for the example taken to 75 blinks
     /* #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_two(&advent_of_code::template::read_file("examples", DAY), &Params::default());
         assert_eq!(result, None);
     } */
 }
@@ -1,13 +1,20 @@
-use itertools::Itertools;
+use advent_of_code::shared::parse::{Cursor, blocks};
+use advent_of_code::template::ParseError;
 use num_rational::Rational64;
 use num_traits::ops::checked::{CheckedMul, CheckedSub};
 
-advent_of_code::solution!(13);
+advent_of_code::solution!(13, params: Params);
 
-const OFFSET: i64 = 10_000_000_000_000;
+advent_of_code::params! {
+    pub struct Params {
+        /// how far the prizes are moved in part two
+        offset: i64 = 10_000_000_000_000,
+    }
+}
 
-pub fn part_one(input: &str) -> Option<u64> {
-    let tokens_spent = parse_input(input)
+pub fn part_one(input: &str, _: &Params) -> Result<u64, ParseError> {
+    let tokens_spent = parse_input(input)?
+        .into_iter()
         .flat_map(|(matrix, solutions)| calculate_solutions(matrix, solutions))
         // filter out any with more than 100 button presses
         .flat_map(|(a, b)| {
@@ -19,12 +26,13 @@ pub fn part_one(input: &str) -> Option<u64> {
         })
         .sum();
 
-    Some(tokens_spent)
+    Ok(tokens_spent)
 }
 
-pub fn part_two(input: &str) -> Option<u64> {
-    let offset = Rational64::from(OFFSET);
-    let tokens_spent = parse_input(input)
+pub fn part_two(input: &str, params: &Params) -> Result<u64, ParseError> {
+    let offset = Rational64::from(params.offset);
+    let tokens_spent = parse_input(input)?
+        .into_iter()
         .map(|(matrix, mut solutions)| {
             solutions.0 += offset;
             solutions.1 += offset;
@@ -34,7 +42,7 @@ pub fn part_two(input: &str) -> Option<u64> {
         .map(|(a, b)| a * 3 + b)
         .sum();
 
-    Some(tokens_spent)
+    Ok(tokens_spent)
 }
 
 fn calculate_solutions(
@@ -88,45 +96,44 @@ fn calculate_pre_y_factor(
     }
 }
 
-fn parse_input(input: &str) -> impl Iterator<Item = ([Rational64; 4], (Rational64, Rational64))> {
-    input
-        .lines()
-        // filter out empty lines
-        .filter(|line| !line.trim().is_empty())
-        // put into groups of 3
-        .tuples()
-        // process each 3-line group and map into our problem inputs
-        .flat_map(|(line_a, line_b, line_p)| {
-            let (ax, ay) = parse_line(line_a, "+")?;
-            let (bx, by) = parse_line(line_b, "+")?;
-            let (px, py) = parse_line(line_p, "=")?;
-            let matrix = [
-                Rational64::from(ax),
-                Rational64::from(bx),
-                Rational64::from(ay),
-                Rational64::from(by),
-            ];
-            let solutions = (Rational64::from(px), Rational64::from(py));
-            Some((matrix, solutions))
-        })
+/// The button matrix of a claw machine, and the prize position to solve it for.
+type Machine = ([Rational64; 4], (Rational64, Rational64));
+
+fn parse_input(input: &str) -> Result<Vec<Machine>, ParseError> {
+    // each machine is a block of 3 lines, separated by empty lines
+    blocks(input, |block| {
+        let lines: Vec<&str> = block.lines().collect();
+        let [line_a, line_b, line_p] = lines[..] else {
+            return Err(ParseError::new("expected a block of 3 lines"));
+        };
+
+        let (ax, ay) = parse_line(line_a, "Button A", "+").map_err(|e| e.at_line(1))?;
+        let (bx, by) = parse_line(line_b, "Button B", "+").map_err(|e| e.at_line(2))?;
+        let (px, py) = parse_line(line_p, "Prize", "=").map_err(|e| e.at_line(3))?;
+        let matrix = [
+            Rational64::from(ax),
+            Rational64::from(bx),
+            Rational64::from(ay),
+            Rational64::from(by),
+        ];
+        let solutions = (Rational64::from(px), Rational64::from(py));
+        Ok((matrix, solutions))
+    })
 }
 
-fn parse_line(line: &str, pat: &'static str) -> Option<(i64, i64)> {
-    let x_pos = line.find(pat)?;
-    let y_pos = {
-        // plus 1 to skip the previous pattern, otherwise
-        // we just get x_pos again
-        let offset = x_pos + 1;
-        line[offset..].find(pat)?
-        // and then re-offset by x_position again
-        + offset
-    };
-
-    // then slice out our numbers and parse them
-    let x = line[x_pos + 1..y_pos - 3].parse().ok()?;
-    let y = line[y_pos + 1..].parse().ok()?;
-
-    Some((x, y))
+/// Parses lines like `Button A: X+94, Y+34` and `Prize: X=8400, Y=5400`.
+fn parse_line(line: &str, label: &str, pat: &str) -> Result<(i64, i64), ParseError> {
+    let mut cursor = Cursor::new(line);
+    cursor.literal(label)?;
+    cursor.literal(": X")?;
+    cursor.literal(pat)?;
+    let x = cursor.integer()?;
+    cursor.literal(", Y")?;
+    cursor.literal(pat)?;
+    let y = cursor.integer()?;
+    cursor.end()?;
+
+    Ok((x, y))
 }
 
 fn get_inv_det(input: [Rational64; 4]) -> Option<Rational64> {
@@ -146,13 +153,30 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(480));
+        let result = part_one(
+            &advent_of_code::template::read_file("examples", DAY),
+            &Params::default(),
+        );
+        assert_eq!(result, Ok(480));
     }
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(875_318_608_908));
+        let result = part_two(
+            &advent_of_code::template::read_file("examples", DAY),
+            &Params::default(),
+        );
+        assert_eq!(result, Ok(875_318_608_908));
+    }
+
+    #[test]
+    fn test_part_two_without_offset() {
+        // without moving the prizes, part two only differs by allowing more than 100 presses.
+        let params = Params { offset: 0 };
+        let result = part_two(
+            &advent_of_code::template::read_file("examples", DAY),
+            &params,
+        );
+        assert_eq!(result, Ok(480));
     }
 }
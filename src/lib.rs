@@ -1,4 +1,12 @@
+// lets the solutions in `src/bin` refer to this crate by name when they are compiled into the solution registry.
+extern crate self as advent_of_code;
+
 pub mod shared;
 pub mod template;
 
+// defined once here, rather than in each binary, so the solution registry does not define it again.
+#[cfg(feature = "dhat-heap")]
+#[global_allocator]
+static ALLOC: dhat::Alloc = dhat::Alloc;
+
 // Use this file to add helper functions and additional modules.
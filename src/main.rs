@@ -1,13 +1,22 @@
-use advent_of_code::template::commands::{all, download, read, scaffold, solve, time};
+use advent_of_code::template::commands::{download, examples, read, replay, scaffold, solve};
+use advent_of_code::template::Puzzle;
 use args::{parse, AppArguments};
 
+#[cfg(feature = "registry")]
+use advent_of_code::template::bench::{BenchConfig, MIN_SAMPLES};
+#[cfg(feature = "registry")]
+use advent_of_code::template::commands::{all, example, status, time, verify};
+#[cfg(feature = "registry")]
+use std::time::Duration;
+
 #[cfg(feature = "today")]
 use advent_of_code::template::Day;
 #[cfg(feature = "today")]
 use std::process;
 
 mod args {
-    use advent_of_code::template::Day;
+    use advent_of_code::template::commands::solve::SolutionArgs;
+    use advent_of_code::template::{Day, Year};
     use std::process;
 
     pub enum AppArguments {
@@ -17,44 +26,122 @@ mod args {
         Read {
             day: Day,
         },
+        Replay {
+            day: Day,
+            part: Option<u8>,
+            fps: Option<u32>,
+        },
+        #[cfg(feature = "registry")]
+        Example {
+            day: Day,
+        },
+        Examples {
+            day: Day,
+            block: Option<usize>,
+            overwrite: bool,
+        },
         Scaffold {
             day: Day,
             download: bool,
             overwrite: bool,
+            template: Option<String>,
         },
         Solve {
             day: Day,
             release: bool,
             dhat: bool,
-            submit: Option<u8>,
+            args: SolutionArgs,
+            watch: bool,
         },
+        #[cfg(feature = "registry")]
         All {
-            release: bool,
+            jobs: Option<usize>,
         },
+        #[cfg(feature = "registry")]
+        Status {
+            format: Option<String>,
+            verify: bool,
+        },
+        #[cfg(feature = "registry")]
         Time {
             all: bool,
             day: Option<Day>,
             store: bool,
+            budget: Option<u64>,
+            max_samples: Option<u128>,
+            compare: bool,
+            threshold: Option<f64>,
+            jobs: Option<usize>,
+        },
+        #[cfg(feature = "registry")]
+        Verify {
+            all: bool,
+            day: Option<Day>,
+            record: bool,
         },
         #[cfg(feature = "today")]
-        Today,
+        Today {
+            template: Option<String>,
+        },
     }
 
-    pub fn parse() -> Result<AppArguments, Box<dyn std::error::Error>> {
+    /// Parses the command and the year it applies to, which defaults to the home year.
+    pub fn parse() -> Result<(Year, AppArguments), Box<dyn std::error::Error>> {
         let mut args = pico_args::Arguments::from_env();
 
-        let app_args = match args.subcommand()?.as_deref() {
+        let subcommand = args.subcommand()?;
+        let year = args
+            .opt_value_from_str("--year")?
+            .unwrap_or_else(Year::home);
+
+        let app_args = match subcommand.as_deref() {
+            // these run the solutions in-process, so they need them compiled into the library.
+            #[cfg(not(feature = "registry"))]
+            Some(x @ ("all" | "time" | "verify" | "example" | "status")) => {
+                eprintln!(
+                    "`{x}` needs the solution registry. Run it as `cargo {x}`, which enables the `registry` feature."
+                );
+                process::exit(1);
+            }
+            #[cfg(feature = "registry")]
             Some("all") => AppArguments::All {
-                release: args.contains("--release"),
+                jobs: args.opt_value_from_str("--jobs")?,
+            },
+            #[cfg(feature = "registry")]
+            Some("status") => AppArguments::Status {
+                format: args.opt_value_from_str("--format")?,
+                verify: args.contains("--verify"),
             },
+            #[cfg(feature = "registry")]
             Some("time") => {
                 let all = args.contains("--all");
                 let store = args.contains("--store");
+                let budget = args.opt_value_from_str("--budget")?;
+                let max_samples = args.opt_value_from_str("--max-samples")?;
+                let compare = args.contains("--compare");
+                let threshold: Option<f64> = args.opt_value_from_str("--threshold")?;
+                let jobs = args.opt_value_from_str("--jobs")?;
 
                 AppArguments::Time {
                     all,
                     day: args.opt_free_from_str()?,
                     store,
+                    budget,
+                    max_samples,
+                    compare,
+                    threshold,
+                    jobs,
+                }
+            }
+            #[cfg(feature = "registry")]
+            Some("verify") => {
+                let all = args.contains("--all");
+                let record = args.contains("--record");
+
+                AppArguments::Verify {
+                    all,
+                    day: args.opt_free_from_str()?,
+                    record,
                 }
             }
             Some("download") => AppArguments::Download {
@@ -63,19 +150,52 @@ mod args {
             Some("read") => AppArguments::Read {
                 day: args.free_from_str()?,
             },
-            Some("scaffold") => AppArguments::Scaffold {
+            Some("replay") => AppArguments::Replay {
                 day: args.free_from_str()?,
-                download: args.contains("--download"),
+                part: args.opt_value_from_str("--part")?,
+                fps: args.opt_value_from_str("--fps")?,
+            },
+            #[cfg(feature = "registry")]
+            Some("example") => AppArguments::Example {
+                day: args.free_from_str()?,
+            },
+            Some("examples") => AppArguments::Examples {
+                day: args.free_from_str()?,
+                block: args.opt_value_from_str("--block")?,
                 overwrite: args.contains("--overwrite"),
             },
-            Some("solve") => AppArguments::Solve {
+            Some("scaffold") => AppArguments::Scaffold {
                 day: args.free_from_str()?,
-                release: args.contains("--release"),
-                submit: args.opt_value_from_str("--submit")?,
-                dhat: args.contains("--dhat"),
+                download: args.contains("--download"),
+                overwrite: args.contains("--overwrite"),
+                template: args.opt_value_from_str("--template")?,
             },
+            Some("solve") => {
+                // `cargo watch-day` puts `--watch` before the day.
+                let watch = args.contains("--watch");
+                // `cargo solve 11 -- --param blinks_part_one=40` separates the solution's arguments.
+                let _ = args.contains("--");
+
+                AppArguments::Solve {
+                    day: args.free_from_str()?,
+                    release: args.contains("--release"),
+                    dhat: args.contains("--dhat"),
+                    args: SolutionArgs {
+                        submit_part: args.opt_value_from_str("--submit")?,
+                        format: args.opt_value_from_str("--format")?,
+                        input: args.opt_value_from_str("--input")?,
+                        params: args.values_from_str("--param")?,
+                        visualize: args.contains("--visualize"),
+                        frames: args.opt_value_from_str("--frames")?,
+                        record: args.contains("--record"),
+                    },
+                    watch,
+                }
+            }
             #[cfg(feature = "today")]
-            Some("today") => AppArguments::Today,
+            Some("today") => AppArguments::Today {
+                template: args.opt_value_from_str("--template")?,
+            },
             Some(x) => {
                 eprintln!("Unknown command: {x}");
                 process::exit(1);
@@ -91,7 +211,7 @@ mod args {
             eprintln!("Warning: unknown argument(s): {remaining:?}.");
         }
 
-        Ok(app_args)
+        Ok((year, app_args))
     }
 }
 
@@ -101,34 +221,101 @@ fn main() {
             eprintln!("Error: {err}");
             std::process::exit(1);
         }
-        Ok(args) => match args {
-            AppArguments::All { release } => all::handle(release),
-            AppArguments::Time { day, all, store } => time::handle(day, all, store),
-            AppArguments::Download { day } => download::handle(day),
-            AppArguments::Read { day } => read::handle(day),
+        Ok((year, args)) => match args {
+            #[cfg(feature = "registry")]
+            AppArguments::All { jobs } => all::handle(year, jobs.unwrap_or(1)),
+            #[cfg(feature = "registry")]
+            AppArguments::Status { format, verify } => {
+                status::handle(year, format.as_deref(), verify);
+            }
+            #[cfg(feature = "registry")]
+            AppArguments::Time {
+                day,
+                all,
+                store,
+                budget,
+                max_samples,
+                compare,
+                threshold,
+                jobs,
+            } => {
+                let mut bench = BenchConfig::default();
+                if let Some(budget) = budget {
+                    bench.budget = Duration::from_millis(budget);
+                }
+                if let Some(max_samples) = max_samples {
+                    bench.max_samples = max_samples.max(MIN_SAMPLES);
+                }
+                let compare_threshold =
+                    compare.then(|| threshold.unwrap_or(time::DEFAULT_COMPARE_THRESHOLD));
+                time::handle(
+                    year,
+                    day,
+                    all,
+                    store,
+                    bench,
+                    compare_threshold,
+                    jobs.unwrap_or(1),
+                );
+            }
+            #[cfg(feature = "registry")]
+            AppArguments::Verify { day, all, record } => verify::handle(year, day, all, record),
+            AppArguments::Download { day } => download::handle(Puzzle::new(year, day)),
+            AppArguments::Read { day } => read::handle(Puzzle::new(year, day)),
+            AppArguments::Replay { day, part, fps } => replay::handle(
+                Puzzle::new(year, day),
+                part,
+                fps.unwrap_or(replay::DEFAULT_FPS),
+            ),
+            #[cfg(feature = "registry")]
+            AppArguments::Example { day } => example::handle(Puzzle::new(year, day)),
+            AppArguments::Examples {
+                day,
+                block,
+                overwrite,
+            } => examples::handle(Puzzle::new(year, day), block, overwrite),
             AppArguments::Scaffold {
                 day,
                 download,
                 overwrite,
+                template,
             } => {
-                scaffold::handle(day, overwrite);
+                let puzzle = Puzzle::new(year, day);
+                // downloading first lets the template use the puzzle's title.
                 if download {
-                    download::handle(day);
+                    download::handle(puzzle);
                 }
+                scaffold::handle(
+                    puzzle,
+                    template
+                        .as_deref()
+                        .unwrap_or(scaffold::DEFAULT_TEMPLATE_NAME),
+                    overwrite,
+                    download,
+                );
             }
             AppArguments::Solve {
                 day,
                 release,
                 dhat,
-                submit,
-            } => solve::handle(day, release, dhat, submit),
+                args,
+                watch,
+            } => solve::handle(Puzzle::new(year, day), release, dhat, &args, watch),
             #[cfg(feature = "today")]
-            AppArguments::Today => {
+            AppArguments::Today { template } => {
                 match Day::today() {
                     Some(day) => {
-                        scaffold::handle(day, false);
-                        download::handle(day);
-                        read::handle(day)
+                        let puzzle = Puzzle::new(year, day);
+                        download::handle(puzzle);
+                        scaffold::handle(
+                            puzzle,
+                            template
+                                .as_deref()
+                                .unwrap_or(scaffold::DEFAULT_TEMPLATE_NAME),
+                            false,
+                            true,
+                        );
+                        read::handle(puzzle)
                     }
                     None => {
                         eprintln!(
@@ -1,3 +1,20 @@
+pub mod canvas;
+pub mod grid;
+pub mod parse;
+pub mod region;
+pub mod search;
+
+pub use canvas::{Canvas, Color};
+pub use grid::{Coord, Grid, GridPoint};
+pub use region::{BoundingBox, Region, Regions};
+
+use crate::template::ParseError;
+
+/// A direction that a [`Coord`] can be shifted in, given as a `(row, col)` step.
+pub trait GridDirection: Copy {
+    fn delta(self) -> (isize, isize);
+}
+
 pub fn match_numeric(ch: char) -> Option<u64> {
     let num: u64 = match ch {
         '0' => 0,
@@ -15,18 +32,10 @@ pub fn match_numeric(ch: char) -> Option<u64> {
     Some(num)
 }
 
-pub fn parse_number_from_str(target: &str) -> Result<u64, String> {
-    let chars: Vec<char> = target.chars().collect();
-    let chars_max_ind = chars.len() - 1;
-    let nums = chars
-        .into_iter()
-        .enumerate()
-        .map(|(ind, ch)| match match_numeric(ch) {
-            Some(num) => Ok(num * 10u64.pow((chars_max_ind - ind).try_into().unwrap())),
-            None => Err("Invalid numeric char in target".to_string()),
-        })
-        .collect::<Result<Vec<u64>, String>>()?;
-    Ok(nums.into_iter().sum())
+/// Parse a number, pointing at the column of the first char that is not a digit on failure.
+/// Empty targets and numbers that overflow a `u64` are errors, too. See [`parse::integer`].
+pub fn parse_number_from_str(target: &str) -> Result<u64, ParseError> {
+    parse::integer(target)
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
@@ -74,397 +83,112 @@ impl CardinalDirection {
     }
 }
 
-#[derive(Debug, PartialEq, Eq, Hash)]
-pub struct GridBounds {
-    pub max_row: usize,
-    pub max_col: usize,
-}
-
-impl GridBounds {
-    pub fn from_input(input: &str) -> GridBounds {
-        let lines: Vec<&str> = input.lines().collect();
-        let max_col = lines[0].len();
-        let max_row = lines.len();
-        GridBounds { max_row, max_col }
+impl GridDirection for CardinalDirection {
+    fn delta(self) -> (isize, isize) {
+        use CardinalDirection::*;
+        match self {
+            Up => (-1, 0),
+            Down => (1, 0),
+            Left => (0, -1),
+            Right => (0, 1),
+        }
     }
 }
 
+/// The eight directions of a grid, i.e. the cardinal directions plus the diagonals.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
-pub struct CardinalCoord<'a> {
-    row: usize,
-    col: usize,
-    grid_bounds: &'a GridBounds,
+pub enum OrdinalDirection {
+    Up,
+    UpRight,
+    Right,
+    DownRight,
+    Down,
+    DownLeft,
+    Left,
+    UpLeft,
 }
 
-impl<'a> CardinalShift for CardinalCoord<'a> {
-    fn shift_left(self) -> Option<Self> {
-        if self.col > 0 {
-            Some(Self {
-                row: self.row,
-                col: self.col - 1,
-                grid_bounds: self.grid_bounds,
-            })
-        } else {
-            None
-        }
-    }
-
-    fn shift_right(self) -> Option<Self> {
-        if self.col < self.grid_bounds.max_col - 1 {
-            Some(Self {
-                row: self.row,
-                col: self.col + 1,
-                grid_bounds: self.grid_bounds,
-            })
-        } else {
-            None
-        }
-    }
-
-    fn shift_up(self) -> Option<Self> {
-        if self.row > 0 {
-            Some(Self {
-                row: self.row - 1,
-                col: self.col,
-                grid_bounds: self.grid_bounds,
-            })
-        } else {
-            None
-        }
-    }
-
-    fn shift_down(self) -> Option<Self> {
-        if self.row < self.grid_bounds.max_row - 1 {
-            Some(Self {
-                row: self.row + 1,
-                col: self.col,
-                grid_bounds: self.grid_bounds,
-            })
-        } else {
-            None
-        }
-    }
-}
+impl OrdinalDirection {
+    /// All eight directions, going clockwise from `Up`.
+    pub const ALL: [OrdinalDirection; 8] = {
+        use OrdinalDirection::*;
+        [Up, UpRight, Right, DownRight, Down, DownLeft, Left, UpLeft]
+    };
 
-impl<'a> RawIndex<'a> for CardinalCoord<'a> {
-    fn to_raw_ind(&self) -> usize {
-        let row_len = self.grid_bounds.max_col;
-        self.row * row_len + self.col
-    }
+    /// The four diagonals, going clockwise from `UpRight`.
+    pub const DIAGONALS: [OrdinalDirection; 4] = {
+        use OrdinalDirection::*;
+        [UpRight, DownRight, DownLeft, UpLeft]
+    };
 
-    fn from_raw_ind(raw_index: usize, bounds: &'a GridBounds) -> Option<Self> {
-        // check if out of bounds
-        if raw_index >= bounds.max_col * bounds.max_row {
-            return None;
+    /// The position of this direction in [`OrdinalDirection::ALL`].
+    const fn index(self) -> usize {
+        use OrdinalDirection::*;
+        match self {
+            Up => 0,
+            UpRight => 1,
+            Right => 2,
+            DownRight => 3,
+            Down => 4,
+            DownLeft => 5,
+            Left => 6,
+            UpLeft => 7,
         }
-        // otherwise return the coord
-        let row_len = bounds.max_col;
-        Some(Self {
-            row: raw_index / row_len,
-            col: raw_index % row_len,
-            grid_bounds: bounds,
-        })
-    }
-}
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[test]
-    fn test_from_raw_ind() {
-        static GRID_BOUNDS: GridBounds = GridBounds {
-            max_row: 5,
-            max_col: 4,
-        };
-        let coord = CardinalCoord::from_raw_ind(6, &GRID_BOUNDS).unwrap();
-        assert_eq!(coord.row, 1);
-        assert_eq!(coord.col, 2);
     }
 
-    #[test]
-    fn test_to_raw_ind() {
-        static GRID_BOUNDS: GridBounds = GridBounds {
-            max_row: 5,
-            max_col: 4,
-        };
-        let coord = CardinalCoord {
-            row: 1,
-            col: 2,
-            grid_bounds: &GRID_BOUNDS,
-        };
-        assert_eq!(coord.to_raw_ind(), 6);
+    /// Rotates by `steps` eighths of a turn, clockwise.
+    fn rotate(self, steps: usize) -> Self {
+        Self::ALL[(self.index() + steps) % 8]
     }
 
-    #[test]
-    fn test_shift_left() {
-        static GRID_BOUNDS: GridBounds = GridBounds {
-            max_row: 5,
-            max_col: 5,
-        };
-        let coord = CardinalCoord {
-            row: 2,
-            col: 2,
-            grid_bounds: &GRID_BOUNDS,
-        };
-        let shifted = coord.shift(CardinalDirection::Left);
-        assert_eq!(
-            shifted,
-            Some(CardinalCoord {
-                row: 2,
-                col: 1,
-                grid_bounds: &GRID_BOUNDS
-            })
-        );
+    pub fn turn_clockwise_45(&self) -> Self {
+        self.rotate(1)
     }
 
-    #[test]
-    fn test_shift_right() {
-        static GRID_BOUNDS: GridBounds = GridBounds {
-            max_row: 5,
-            max_col: 5,
-        };
-        let coord = CardinalCoord {
-            row: 2,
-            col: 2,
-            grid_bounds: &GRID_BOUNDS,
-        };
-        let shifted = coord.shift(CardinalDirection::Right);
-        assert_eq!(
-            shifted,
-            Some(CardinalCoord {
-                row: 2,
-                col: 3,
-                grid_bounds: &GRID_BOUNDS
-            })
-        );
+    pub fn turn_anti_clockwise_45(&self) -> Self {
+        self.rotate(7)
     }
 
-    #[test]
-    fn test_shift_up() {
-        static GRID_BOUNDS: GridBounds = GridBounds {
-            max_row: 5,
-            max_col: 5,
-        };
-        let coord = CardinalCoord {
-            row: 2,
-            col: 2,
-            grid_bounds: &GRID_BOUNDS,
-        };
-        let shifted = coord.shift(CardinalDirection::Up);
-        assert_eq!(
-            shifted,
-            Some(CardinalCoord {
-                row: 1,
-                col: 2,
-                grid_bounds: &GRID_BOUNDS
-            })
-        );
-    }
-
-    #[test]
-    fn test_shift_down() {
-        static GRID_BOUNDS: GridBounds = GridBounds {
-            max_row: 5,
-            max_col: 5,
-        };
-        let coord = CardinalCoord {
-            row: 2,
-            col: 2,
-            grid_bounds: &GRID_BOUNDS,
-        };
-        let shifted = coord.shift(CardinalDirection::Down);
-        assert_eq!(
-            shifted,
-            Some(CardinalCoord {
-                row: 3,
-                col: 2,
-                grid_bounds: &GRID_BOUNDS
-            })
-        );
-    }
-
-    #[test]
-    fn test_shift_left_out_of_bounds() {
-        static GRID_BOUNDS: GridBounds = GridBounds {
-            max_row: 5,
-            max_col: 5,
-        };
-        let coord = CardinalCoord {
-            row: 2,
-            col: 0,
-            grid_bounds: &GRID_BOUNDS,
-        };
-        let shifted = coord.shift(CardinalDirection::Left);
-        assert_eq!(shifted, None);
+    pub fn turn_clockwise(&self) -> Self {
+        self.rotate(2)
     }
 
-    #[test]
-    fn test_shift_right_out_of_bounds() {
-        static GRID_BOUNDS: GridBounds = GridBounds {
-            max_row: 5,
-            max_col: 5,
-        };
-        let coord = CardinalCoord {
-            row: 2,
-            col: 4,
-            grid_bounds: &GRID_BOUNDS,
-        };
-        let shifted = coord.shift(CardinalDirection::Right);
-        assert_eq!(shifted, None);
+    pub fn turn_anti_clockwise(&self) -> Self {
+        self.rotate(6)
     }
 
-    #[test]
-    fn test_shift_up_out_of_bounds() {
-        static GRID_BOUNDS: GridBounds = GridBounds {
-            max_row: 5,
-            max_col: 5,
-        };
-        let coord = CardinalCoord {
-            row: 0,
-            col: 2,
-            grid_bounds: &GRID_BOUNDS,
-        };
-        let shifted = coord.shift(CardinalDirection::Up);
-        assert_eq!(shifted, None);
+    pub fn flip(&self) -> Self {
+        self.rotate(4)
     }
 
-    #[test]
-    fn test_shift_down_out_of_bounds() {
-        static GRID_BOUNDS: GridBounds = GridBounds {
-            max_row: 5,
-            max_col: 5,
-        };
-        let coord = CardinalCoord {
-            row: 4,
-            col: 2,
-            grid_bounds: &GRID_BOUNDS,
-        };
-        let shifted = coord.shift(CardinalDirection::Down);
-        assert_eq!(shifted, None);
+    pub fn is_diagonal(&self) -> bool {
+        self.index() % 2 == 1
     }
 }
 
-pub trait RawIndex<'a>
-where
-    Self: Sized,
-{
-    fn to_raw_ind(&self) -> usize;
-    fn from_raw_ind(raw_index: usize, bounds: &'a GridBounds) -> Option<Self>;
-}
-
-pub trait CardinalShift
-where
-    Self: Sized,
-{
-    fn shift(self, card_dir: CardinalDirection) -> Option<Self> {
-        use CardinalDirection::*;
-        match card_dir {
-            Up => self.shift_up(),
-            Down => self.shift_down(),
-            Left => self.shift_left(),
-            Right => self.shift_right(),
+impl GridDirection for OrdinalDirection {
+    fn delta(self) -> (isize, isize) {
+        use OrdinalDirection::*;
+        match self {
+            Up => (-1, 0),
+            UpRight => (-1, 1),
+            Right => (0, 1),
+            DownRight => (1, 1),
+            Down => (1, 0),
+            DownLeft => (1, -1),
+            Left => (0, -1),
+            UpLeft => (-1, -1),
         }
     }
-
-    fn shift_up(self) -> Option<Self>;
-
-    fn shift_down(self) -> Option<Self>;
-
-    fn shift_left(self) -> Option<Self>;
-
-    fn shift_right(self) -> Option<Self>;
 }
 
-use std::marker::PhantomData;
-
-pub use grid::Grid;
-mod grid {
-    use std::marker::PhantomData;
-
-    use super::{
-        CardinalDirection, CardinalNeighbors, CardinalShift, GridBounds, GridPoint, RawIndex,
-    };
-
-    /// A generic grid component for solving AoC grid problems. The vec is laid out in order, under the assumption that
-    /// there is a fixed MxN grid size.
-    pub struct Grid<'a, T> {
-        pub contents: Vec<T>,
-        pub grid_bounds: &'a GridBounds,
-    }
-
-    impl<'a, T: Copy> Grid<'a, T> {
-        pub fn new(contents: Vec<T>, grid_bounds: &'a GridBounds) -> Self {
-            Self {
-                contents,
-                grid_bounds,
-            }
-        }
-
-        pub fn get_from_raw_ind(&self, raw_ind: usize) -> Option<&T> {
-            self.contents.get(raw_ind)
-        }
-
-        pub fn get_from_coord<C: RawIndex<'a>>(&self, coord: C) -> Option<&T> {
-            let raw_ind = coord.to_raw_ind();
-            self.get_from_raw_ind(raw_ind)
-        }
-
-        pub fn set_at_coord<C: RawIndex<'a>>(&mut self, coord: C, val: T) {
-            let raw_ind = coord.to_raw_ind();
-            self.contents[raw_ind] = val;
-        }
-
-        pub fn get_map_neighbors_from_coord<C>(
-            &self,
-            coord: C,
-        ) -> CardinalNeighbors<Option<(C, &T)>>
-        where
-            C: CardinalShift + RawIndex<'a> + Copy,
-        {
-            CardinalDirection::ALL
-                .into_iter()
-                .map(|card_dir| coord.shift(card_dir))
-                .map(|new_coord| {
-                    new_coord.map(|c| (c, self.get_from_coord(c).expect("must be valid coord")))
-                })
-                .collect()
-        }
-
-        /// Returns an iterator over the contents of the grid
-        pub fn iter(&self) -> impl Iterator<Item = &T> {
-            self.contents.iter()
-        }
-
-        pub fn coord_iter<C: RawIndex<'a> + Copy>(&self) -> impl Iterator<Item = C> {
-            (0..self.contents.len())
-                .map(move |ind| C::from_raw_ind(ind, self.grid_bounds).expect("valid coord"))
-        }
-
-        /// Returns an iterator over the grid points, which are tuples of (coord, point)
-        pub fn grid_point_iter<C: RawIndex<'a> + Copy>(
-            &self,
-        ) -> impl Iterator<Item = GridPoint<'a, C, T>> {
-            self.contents.iter().enumerate().map(move |(ind, &point)| {
-                let loc = C::from_raw_ind(ind, self.grid_bounds).expect("valid coord");
-                GridPoint {
-                    loc,
-                    point,
-                    _marker: PhantomData,
-                }
-            })
-        }
-
-        pub fn get_grid_point_from_coord<C: RawIndex<'a> + Copy>(
-            &self,
-            coord: C,
-        ) -> Option<GridPoint<'a, C, T>> {
-            let point = self.get_from_coord(coord)?;
-            Some(GridPoint {
-                loc: coord,
-                point: *point,
-                _marker: PhantomData,
-            })
+impl From<CardinalDirection> for OrdinalDirection {
+    fn from(value: CardinalDirection) -> Self {
+        match value {
+            CardinalDirection::Up => OrdinalDirection::Up,
+            CardinalDirection::Down => OrdinalDirection::Down,
+            CardinalDirection::Left => OrdinalDirection::Left,
+            CardinalDirection::Right => OrdinalDirection::Right,
         }
     }
 }
@@ -487,19 +211,71 @@ impl<A: Copy + Default> FromIterator<A> for CardinalNeighbors<A> {
     }
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
-pub struct GridPoint<'a, C: RawIndex<'a> + Copy, T: Copy> {
-    pub loc: C,
-    pub point: T,
-    _marker: PhantomData<&'a ()>,
+/// The eight neighbours of a grid cell, in the order of [`OrdinalDirection::ALL`].
+pub struct OrdinalNeighbors<T>([T; 8]);
+
+impl<T> OrdinalNeighbors<T> {
+    pub fn iter(&self) -> impl Iterator<Item = &T> {
+        self.0.iter()
+    }
+
+    /// Returns the neighbour in the given direction.
+    pub fn get(&self, dir: OrdinalDirection) -> &T {
+        &self.0[dir.index()]
+    }
 }
 
-impl<'a, C: RawIndex<'a> + Copy, T: Copy> GridPoint<'a, C, T> {
-    pub fn from(loc: C, point: T) -> Self {
-        GridPoint {
-            loc,
-            point,
-            _marker: PhantomData,
+impl<A: Copy + Default> FromIterator<A> for OrdinalNeighbors<A> {
+    fn from_iter<T: IntoIterator<Item = A>>(iter: T) -> Self {
+        let mut inner: [A; 8] = [A::default(); 8];
+        for (ind, element) in (0..8).zip(iter) {
+            inner[ind] = element;
         }
+        Self(inner)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_number_from_str() {
+        assert_eq!(parse_number_from_str("3267"), Ok(3267));
+        assert!(parse_number_from_str("").is_err());
+        assert!(parse_number_from_str("18446744073709551616").is_err());
+        assert_eq!(parse_number_from_str("4x").unwrap_err().column, Some(2));
+    }
+
+    #[test]
+    fn test_ordinal_rotations() {
+        use OrdinalDirection::*;
+        assert_eq!(Up.turn_clockwise_45(), UpRight);
+        assert_eq!(Up.turn_anti_clockwise_45(), UpLeft);
+        assert_eq!(UpLeft.turn_clockwise(), UpRight);
+        assert_eq!(DownRight.turn_anti_clockwise(), UpRight);
+        assert_eq!(DownLeft.flip(), UpRight);
+    }
+
+    #[test]
+    fn test_ordinal_rotations_match_cardinal() {
+        for dir in CardinalDirection::ALL {
+            assert_eq!(
+                OrdinalDirection::from(dir.turn_clockwise()),
+                OrdinalDirection::from(dir).turn_clockwise()
+            );
+            assert_eq!(dir.delta(), OrdinalDirection::from(dir).delta());
+        }
+    }
+
+    #[test]
+    fn test_is_diagonal() {
+        assert_eq!(
+            OrdinalDirection::ALL
+                .into_iter()
+                .filter(OrdinalDirection::is_diagonal)
+                .collect::<Vec<_>>(),
+            OrdinalDirection::DIAGONALS.to_vec()
+        );
     }
 }
@@ -0,0 +1,238 @@
+//! Text pictures of [`Grid`]s and strips for the [`visualize`](crate::template::visualize) channel,
+//! with overlays such as a path, markers and coloured regions.
+use std::{collections::HashSet, fmt::Display};
+
+use super::{Coord, Grid};
+
+/// The ANSI colours overlays can use.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Color {
+    Red,
+    Green,
+    Yellow,
+    Blue,
+    Magenta,
+    Cyan,
+}
+
+impl Color {
+    pub const ALL: [Color; 6] = [
+        Color::Red,
+        Color::Green,
+        Color::Yellow,
+        Color::Blue,
+        Color::Magenta,
+        Color::Cyan,
+    ];
+
+    /// The offset of the colour in the ANSI codes, `30 + code` for text and `40 + code` for backgrounds.
+    fn code(self) -> u8 {
+        match self {
+            Color::Red => 1,
+            Color::Green => 2,
+            Color::Yellow => 3,
+            Color::Blue => 4,
+            Color::Magenta => 5,
+            Color::Cyan => 6,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Cell {
+    ch: char,
+    fg: Option<Color>,
+    bg: Option<Color>,
+}
+
+impl Cell {
+    fn plain(ch: char) -> Self {
+        Self {
+            ch,
+            fg: None,
+            bg: None,
+        }
+    }
+}
+
+/// A grid of characters with colours, drawn from a [`Grid`] or a strip and then overlaid:
+///
+/// ```ignore
+/// let canvas = Canvas::from_grid(&lab, |&ch| ch)
+///     .path(visited, 'X', Color::Yellow)
+///     .mark(guard, '^', Color::Red);
+/// visualize::frame("guard turns", || canvas);
+/// ```
+///
+/// Overlays are applied in order, so later ones win. Coordinates outside the canvas are ignored.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Canvas {
+    cells: Grid<Cell>,
+}
+
+impl Canvas {
+    pub fn from_grid<T>(grid: &Grid<T>, mut draw: impl FnMut(&T) -> char) -> Self {
+        Self {
+            cells: grid.map(|value| Cell::plain(draw(value))),
+        }
+    }
+
+    /// A canvas of a single row, e.g. a disk's blocks. Its cells are at `Coord::new(0, index)`.
+    pub fn from_strip<T>(strip: &[T], draw: impl FnMut(&T) -> char) -> Self {
+        let cells = strip.iter().map(draw).map(Cell::plain).collect();
+        Self {
+            cells: Grid::new(cells, 1, strip.len()),
+        }
+    }
+
+    /// Draw a single marker, e.g. the guard.
+    #[must_use]
+    pub fn mark(mut self, coord: Coord, ch: char, color: Color) -> Self {
+        if let Some(cell) = self.cells.get_mut_from_coord(coord) {
+            *cell = Cell {
+                ch,
+                fg: Some(color),
+                ..*cell
+            };
+        }
+        self
+    }
+
+    /// Draw the same marker at every coordinate, e.g. the cells a path went through.
+    #[must_use]
+    pub fn path(self, coords: impl IntoIterator<Item = Coord>, ch: char, color: Color) -> Self {
+        coords
+            .into_iter()
+            .fold(self, |canvas, coord| canvas.mark(coord, ch, color))
+    }
+
+    /// Colour the background of a cell, keeping its character.
+    #[must_use]
+    pub fn highlight(mut self, coord: Coord, color: Color) -> Self {
+        if let Some(cell) = self.cells.get_mut_from_coord(coord) {
+            cell.bg = Some(color);
+        }
+        self
+    }
+
+    /// Colour the background of every region, as labelled by [`Grid::label_regions`].
+    /// Touching regions get different colours where the palette allows it.
+    #[must_use]
+    pub fn regions(mut self, labels: &Grid<usize>) -> Self {
+        let colors = region_colors(labels);
+
+        for coord in labels.coord_iter() {
+            let color = colors[labels[coord]];
+            if let Some(cell) = self.cells.get_mut_from_coord(coord) {
+                cell.bg = Some(color);
+            }
+        }
+        self
+    }
+}
+
+/// Greedily pick a colour for every region that none of its neighbours picked before it.
+fn region_colors(labels: &Grid<usize>) -> Vec<Color> {
+    let count = labels.iter().max().map_or(0, |&max| max + 1);
+    let mut touching = HashSet::new();
+
+    for coord in labels.coord_iter() {
+        let right = Coord::new(coord.row, coord.col + 1);
+        let down = Coord::new(coord.row + 1, coord.col);
+
+        for neighbor in [right, down] {
+            if let Some(&other) = labels.get_from_coord(neighbor)
+                && other != labels[coord]
+            {
+                touching.insert((labels[coord].min(other), labels[coord].max(other)));
+            }
+        }
+    }
+
+    let mut colors: Vec<Color> = Vec::with_capacity(count);
+    for id in 0..count {
+        let taken: Vec<Color> = (0..id)
+            .filter(|&other| touching.contains(&(other, id)))
+            .map(|other| colors[other])
+            .collect();
+        let color = Color::ALL
+            .into_iter()
+            .find(|color| !taken.contains(color))
+            .unwrap_or(Color::ALL[id % Color::ALL.len()]);
+        colors.push(color);
+    }
+
+    colors
+}
+
+impl Display for Canvas {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for (index, row) in self.cells.rows_iter().enumerate() {
+            if index > 0 {
+                writeln!(f)?;
+            }
+
+            for cell in row {
+                let codes: Vec<String> = [
+                    cell.fg.map(|color| 30 + color.code()),
+                    cell.bg.map(|color| 40 + color.code()),
+                ]
+                .into_iter()
+                .flatten()
+                .map(|code| code.to_string())
+                .collect();
+
+                if codes.is_empty() {
+                    write!(f, "{}", cell.ch)?;
+                } else {
+                    write!(f, "\x1b[{}m{}\x1b[0m", codes.join(";"), cell.ch)?;
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::template::visualize::strip_ansi;
+
+    #[test]
+    fn test_draws_overlays() {
+        let grid = Grid::from_input("..#\n...", |ch| ch);
+        let canvas = Canvas::from_grid(&grid, |&ch| ch)
+            .path([Coord::new(1, 0), Coord::new(1, 1)], 'X', Color::Yellow)
+            .mark(Coord::new(1, 1), '>', Color::Red)
+            .mark(Coord::new(5, 5), '?', Color::Red);
+
+        assert_eq!(strip_ansi(&canvas.to_string()), "..#\nX>.");
+        assert!(canvas.to_string().contains("\x1b[31m>\x1b[0m"));
+    }
+
+    #[test]
+    fn test_draws_strips() {
+        let canvas = Canvas::from_strip(&[Some(0), None, Some(1)], |block| match block {
+            Some(id) => char::from_digit(*id, 10).unwrap(),
+            None => '.',
+        })
+        .highlight(Coord::new(0, 1), Color::Blue);
+
+        assert_eq!(canvas.to_string(), "0\x1b[44m.\x1b[0m1");
+
+        let empty = Canvas::from_strip(&[] as &[u8], |_| '.');
+        assert_eq!(empty.to_string(), "");
+    }
+
+    #[test]
+    fn test_colours_touching_regions_differently() {
+        let garden = Grid::from_input("AAB\nCAB\nCCB", |ch| ch);
+        let regions = garden.label_regions(|a, b| a == b);
+        let colors = region_colors(&regions.labels);
+
+        assert_eq!(colors.len(), 3);
+        assert_ne!(colors[0], colors[1]);
+        assert_ne!(colors[0], colors[2]);
+        assert_ne!(colors[1], colors[2]);
+    }
+}
@@ -0,0 +1,561 @@
+use std::{
+    error::Error,
+    fmt::Display,
+    ops::{Index, IndexMut},
+    str::FromStr,
+};
+
+use super::{
+    CardinalDirection, CardinalNeighbors, GridDirection, OrdinalDirection, OrdinalNeighbors,
+};
+
+/// A plain `(row, col)` position. Coordinates do not know about any bounds themselves,
+/// they are checked against a [`Grid`] whenever they are used to look something up.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct Coord {
+    pub row: usize,
+    pub col: usize,
+}
+
+impl Coord {
+    pub const fn new(row: usize, col: usize) -> Self {
+        Self { row, col }
+    }
+
+    pub fn manhattan_distance(self, other: Coord) -> usize {
+        self.row.abs_diff(other.row) + self.col.abs_diff(other.col)
+    }
+}
+
+impl From<(usize, usize)> for Coord {
+    fn from((row, col): (usize, usize)) -> Self {
+        Self { row, col }
+    }
+}
+
+impl From<Coord> for (usize, usize) {
+    fn from(value: Coord) -> Self {
+        (value.row, value.col)
+    }
+}
+
+/// A generic grid component for solving AoC grid problems. The vec is laid out in row order,
+/// and the grid owns its own dimensions, so it can be freely returned from parsers or stored in structs.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Grid<T> {
+    contents: Vec<T>,
+    rows: usize,
+    cols: usize,
+}
+
+impl<T> Grid<T> {
+    /// Creates a grid from its contents in row order.
+    ///
+    /// # Panics
+    /// Panics if the number of elements does not match `rows * cols`.
+    pub fn new(contents: Vec<T>, rows: usize, cols: usize) -> Self {
+        assert_eq!(
+            contents.len(),
+            rows * cols,
+            "grid contents must have exactly rows * cols elements"
+        );
+        Self {
+            contents,
+            rows,
+            cols,
+        }
+    }
+
+    /// Parses a grid from puzzle input, mapping each char to a cell with `f`.
+    ///
+    /// # Panics
+    /// Panics if the input is empty or the lines are not all the same length.
+    pub fn from_input(input: &str, mut f: impl FnMut(char) -> T) -> Self {
+        match Self::try_from_input(input, |ch| Ok::<T, ()>(f(ch))) {
+            Ok(grid) => grid,
+            Err(e) => panic!("invalid grid input: {e}"),
+        }
+    }
+
+    /// Parses a grid from puzzle input, mapping each char to a cell with the fallible `f`.
+    pub fn try_from_input<E>(
+        input: &str,
+        mut f: impl FnMut(char) -> Result<T, E>,
+    ) -> Result<Self, GridParseError> {
+        let mut contents = vec![];
+        let mut rows = 0;
+        let mut cols = None;
+
+        for (row, line) in input.lines().enumerate() {
+            let mut line_len = 0;
+            for (col, ch) in line.chars().enumerate() {
+                let cell = f(ch).map_err(|_| GridParseError::InvalidChar { row, col, ch })?;
+                contents.push(cell);
+                line_len += 1;
+            }
+            match cols {
+                None => cols = Some(line_len),
+                Some(expected) if expected != line_len => {
+                    return Err(GridParseError::RaggedLine {
+                        row,
+                        expected,
+                        found: line_len,
+                    });
+                }
+                Some(_) => {}
+            }
+            rows += 1;
+        }
+
+        match cols {
+            Some(cols) if cols > 0 => Ok(Self {
+                contents,
+                rows,
+                cols,
+            }),
+            _ => Err(GridParseError::Empty),
+        }
+    }
+
+    pub fn rows(&self) -> usize {
+        self.rows
+    }
+
+    pub fn cols(&self) -> usize {
+        self.cols
+    }
+
+    pub fn len(&self) -> usize {
+        self.contents.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.contents.is_empty()
+    }
+
+    /// Returns true if the coordinate lies inside the grid.
+    pub fn contains(&self, coord: Coord) -> bool {
+        coord.row < self.rows && coord.col < self.cols
+    }
+
+    pub fn to_raw_ind(&self, coord: Coord) -> Option<usize> {
+        self.contains(coord)
+            .then_some(coord.row * self.cols + coord.col)
+    }
+
+    pub fn coord_from_raw_ind(&self, raw_ind: usize) -> Option<Coord> {
+        (raw_ind < self.contents.len()).then_some(Coord {
+            row: raw_ind / self.cols,
+            col: raw_ind % self.cols,
+        })
+    }
+
+    pub fn get_from_raw_ind(&self, raw_ind: usize) -> Option<&T> {
+        self.contents.get(raw_ind)
+    }
+
+    pub fn get_from_coord(&self, coord: Coord) -> Option<&T> {
+        self.to_raw_ind(coord)
+            .and_then(|raw_ind| self.contents.get(raw_ind))
+    }
+
+    pub fn get_mut_from_coord(&mut self, coord: Coord) -> Option<&mut T> {
+        self.to_raw_ind(coord)
+            .and_then(|raw_ind| self.contents.get_mut(raw_ind))
+    }
+
+    /// # Panics
+    /// Panics if the coordinate is out of bounds.
+    pub fn set_at_coord(&mut self, coord: Coord, val: T) {
+        self[coord] = val;
+    }
+
+    /// Shifts the coordinate 1 step in the given direction, which can be
+    /// either a [`CardinalDirection`] or an [`OrdinalDirection`].
+    /// Returns `None` if that would leave the grid.
+    pub fn shift<D: GridDirection>(&self, coord: Coord, dir: D) -> Option<Coord> {
+        self.shift_by(coord, dir.delta())
+    }
+
+    /// Shifts the coordinate by an arbitrary `(row, col)` step.
+    /// Returns `None` if that would leave the grid.
+    pub fn shift_by(&self, coord: Coord, (d_row, d_col): (isize, isize)) -> Option<Coord> {
+        let shifted = Coord::new(
+            coord.row.checked_add_signed(d_row)?,
+            coord.col.checked_add_signed(d_col)?,
+        );
+        self.contains(shifted).then_some(shifted)
+    }
+
+    /// Returns an iterator walking from the coordinate in a straight line in the given direction,
+    /// until it reaches the edge of the grid. The starting coordinate itself is not included.
+    pub fn ray<D: GridDirection>(&self, coord: Coord, dir: D) -> impl Iterator<Item = Coord> {
+        std::iter::successors(self.shift(coord, dir), move |&c| self.shift(c, dir))
+    }
+
+    /// Returns the neighbours in the order of [`CardinalDirection::ALL`],
+    /// where neighbours outside of the grid are `None`.
+    pub fn get_map_neighbors_from_coord(
+        &self,
+        coord: Coord,
+    ) -> CardinalNeighbors<Option<(Coord, &T)>> {
+        CardinalDirection::ALL
+            .into_iter()
+            .map(|card_dir| self.shift(coord, card_dir))
+            .map(|new_coord| {
+                new_coord.map(|c| (c, self.get_from_coord(c).expect("must be valid coord")))
+            })
+            .collect()
+    }
+
+    /// Returns an iterator over the in-bounds cardinal neighbours of the coordinate,
+    /// which is handy as a successor function for [`search`](super::search).
+    pub fn neighbor_coords(&self, coord: Coord) -> impl Iterator<Item = Coord> {
+        CardinalDirection::ALL
+            .into_iter()
+            .filter_map(move |dir| self.shift(coord, dir))
+    }
+
+    /// Returns all eight neighbours in the order of [`OrdinalDirection::ALL`],
+    /// where neighbours outside of the grid are `None`.
+    pub fn get_all_neighbors_from_coord(
+        &self,
+        coord: Coord,
+    ) -> OrdinalNeighbors<Option<(Coord, &T)>> {
+        OrdinalDirection::ALL
+            .into_iter()
+            .map(|dir| self.shift(coord, dir))
+            .map(|new_coord| {
+                new_coord.map(|c| (c, self.get_from_coord(c).expect("must be valid coord")))
+            })
+            .collect()
+    }
+
+    /// Returns an iterator over the contents of the grid
+    pub fn iter(&self) -> impl Iterator<Item = &T> {
+        self.contents.iter()
+    }
+
+    /// Returns an iterator over every coordinate in the grid, in row order
+    pub fn coord_iter(&self) -> impl Iterator<Item = Coord> + use<T> {
+        let cols = self.cols;
+        (0..self.contents.len()).map(move |ind| Coord {
+            row: ind / cols,
+            col: ind % cols,
+        })
+    }
+
+    /// Returns an iterator over the rows of the grid as slices, which are empty for a grid without columns
+    pub fn rows_iter(&self) -> impl Iterator<Item = &[T]> {
+        (0..self.rows).map(|row| &self.contents[row * self.cols..(row + 1) * self.cols])
+    }
+
+    pub fn row(&self, row: usize) -> Option<&[T]> {
+        (row < self.rows).then(|| &self.contents[row * self.cols..(row + 1) * self.cols])
+    }
+
+    /// Returns the coordinate of the first cell (in row order) matching the predicate
+    pub fn position(&self, predicate: impl Fn(&T) -> bool) -> Option<Coord> {
+        self.contents
+            .iter()
+            .position(predicate)
+            .and_then(|raw_ind| self.coord_from_raw_ind(raw_ind))
+    }
+
+    /// Creates a new grid of the same dimensions by mapping every cell
+    pub fn map<U>(&self, f: impl FnMut(&T) -> U) -> Grid<U> {
+        Grid {
+            contents: self.contents.iter().map(f).collect(),
+            rows: self.rows,
+            cols: self.cols,
+        }
+    }
+}
+
+impl<T: Clone> Grid<T> {
+    /// Creates a grid with every cell set to `val`
+    pub fn filled(rows: usize, cols: usize, val: T) -> Self {
+        Self {
+            contents: vec![val; rows * cols],
+            rows,
+            cols,
+        }
+    }
+}
+
+impl<T: Copy> Grid<T> {
+    /// Returns an iterator over the grid points, which are tuples of (coord, point)
+    pub fn grid_point_iter(&self) -> impl Iterator<Item = GridPoint<T>> {
+        self.coord_iter()
+            .zip(self.contents.iter())
+            .map(|(loc, &point)| GridPoint { loc, point })
+    }
+
+    pub fn get_grid_point_from_coord(&self, coord: Coord) -> Option<GridPoint<T>> {
+        let point = self.get_from_coord(coord)?;
+        Some(GridPoint {
+            loc: coord,
+            point: *point,
+        })
+    }
+}
+
+impl<T> Index<Coord> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, coord: Coord) -> &Self::Output {
+        self.get_from_coord(coord)
+            .unwrap_or_else(|| panic!("coord {coord:?} is out of bounds"))
+    }
+}
+
+impl<T> IndexMut<Coord> for Grid<T> {
+    fn index_mut(&mut self, coord: Coord) -> &mut Self::Output {
+        self.get_mut_from_coord(coord)
+            .unwrap_or_else(|| panic!("coord {coord:?} is out of bounds"))
+    }
+}
+
+impl<T: TryFrom<char>> FromStr for Grid<T> {
+    type Err = GridParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::try_from_input(s, T::try_from)
+    }
+}
+
+/// An error which can be returned when parsing a [`Grid`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum GridParseError {
+    Empty,
+    RaggedLine {
+        row: usize,
+        expected: usize,
+        found: usize,
+    },
+    InvalidChar {
+        row: usize,
+        col: usize,
+        ch: char,
+    },
+}
+
+impl Error for GridParseError {}
+
+impl Display for GridParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            GridParseError::Empty => write!(f, "grid input is empty"),
+            GridParseError::RaggedLine {
+                row,
+                expected,
+                found,
+            } => write!(f, "line {} has {found} cells, expected {expected}", row + 1),
+            GridParseError::InvalidChar { row, col, ch } => write!(
+                f,
+                "invalid char {ch:?} at line {}, column {}",
+                row + 1,
+                col + 1
+            ),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct GridPoint<T: Copy> {
+    pub loc: Coord,
+    pub point: T,
+}
+
+impl<T: Copy> GridPoint<T> {
+    pub fn from(loc: Coord, point: T) -> Self {
+        GridPoint { loc, point }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn empty_grid(rows: usize, cols: usize) -> Grid<()> {
+        Grid::filled(rows, cols, ())
+    }
+
+    #[test]
+    fn test_from_raw_ind() {
+        let grid = empty_grid(5, 4);
+        let coord = grid.coord_from_raw_ind(6).unwrap();
+        assert_eq!(coord.row, 1);
+        assert_eq!(coord.col, 2);
+    }
+
+    #[test]
+    fn test_from_raw_ind_out_of_bounds() {
+        let grid = empty_grid(5, 4);
+        assert_eq!(grid.coord_from_raw_ind(20), None);
+    }
+
+    #[test]
+    fn test_to_raw_ind() {
+        let grid = empty_grid(5, 4);
+        assert_eq!(grid.to_raw_ind(Coord::new(1, 2)), Some(6));
+    }
+
+    #[test]
+    fn test_to_raw_ind_out_of_bounds() {
+        let grid = empty_grid(5, 4);
+        assert_eq!(grid.to_raw_ind(Coord::new(1, 4)), None);
+    }
+
+    #[test]
+    fn test_shift_left() {
+        let grid = empty_grid(5, 5);
+        let shifted = grid.shift(Coord::new(2, 2), CardinalDirection::Left);
+        assert_eq!(shifted, Some(Coord::new(2, 1)));
+    }
+
+    #[test]
+    fn test_shift_right() {
+        let grid = empty_grid(5, 5);
+        let shifted = grid.shift(Coord::new(2, 2), CardinalDirection::Right);
+        assert_eq!(shifted, Some(Coord::new(2, 3)));
+    }
+
+    #[test]
+    fn test_shift_up() {
+        let grid = empty_grid(5, 5);
+        let shifted = grid.shift(Coord::new(2, 2), CardinalDirection::Up);
+        assert_eq!(shifted, Some(Coord::new(1, 2)));
+    }
+
+    #[test]
+    fn test_shift_down() {
+        let grid = empty_grid(5, 5);
+        let shifted = grid.shift(Coord::new(2, 2), CardinalDirection::Down);
+        assert_eq!(shifted, Some(Coord::new(3, 2)));
+    }
+
+    #[test]
+    fn test_shift_left_out_of_bounds() {
+        let grid = empty_grid(5, 5);
+        let shifted = grid.shift(Coord::new(2, 0), CardinalDirection::Left);
+        assert_eq!(shifted, None);
+    }
+
+    #[test]
+    fn test_shift_right_out_of_bounds() {
+        let grid = empty_grid(5, 5);
+        let shifted = grid.shift(Coord::new(2, 4), CardinalDirection::Right);
+        assert_eq!(shifted, None);
+    }
+
+    #[test]
+    fn test_shift_up_out_of_bounds() {
+        let grid = empty_grid(5, 5);
+        let shifted = grid.shift(Coord::new(0, 2), CardinalDirection::Up);
+        assert_eq!(shifted, None);
+    }
+
+    #[test]
+    fn test_shift_down_out_of_bounds() {
+        let grid = empty_grid(5, 5);
+        let shifted = grid.shift(Coord::new(4, 2), CardinalDirection::Down);
+        assert_eq!(shifted, None);
+    }
+
+    #[test]
+    fn test_shift_diagonal() {
+        let grid = empty_grid(5, 5);
+        let shifted = grid.shift(Coord::new(2, 2), OrdinalDirection::DownLeft);
+        assert_eq!(shifted, Some(Coord::new(3, 1)));
+    }
+
+    #[test]
+    fn test_shift_diagonal_out_of_bounds() {
+        let grid = empty_grid(5, 5);
+        let shifted = grid.shift(Coord::new(0, 2), OrdinalDirection::UpRight);
+        assert_eq!(shifted, None);
+        let shifted = grid.shift(Coord::new(2, 4), OrdinalDirection::UpRight);
+        assert_eq!(shifted, None);
+    }
+
+    #[test]
+    fn test_ray() {
+        let grid = empty_grid(5, 5);
+        let ray: Vec<Coord> = grid
+            .ray(Coord::new(1, 1), OrdinalDirection::DownRight)
+            .collect();
+        assert_eq!(
+            ray,
+            vec![Coord::new(2, 2), Coord::new(3, 3), Coord::new(4, 4)]
+        );
+    }
+
+    #[test]
+    fn test_from_input() {
+        let grid = Grid::from_input("abc\ndef\n", |ch| ch);
+        assert_eq!(grid.rows(), 2);
+        assert_eq!(grid.cols(), 3);
+        assert_eq!(grid[Coord::new(1, 0)], 'd');
+        assert_eq!(grid.row(0), Some(&['a', 'b', 'c'][..]));
+    }
+
+    #[test]
+    fn test_rows_iter_without_columns() {
+        let grid: Grid<char> = Grid::new(vec![], 2, 0);
+        assert_eq!(grid.rows_iter().collect::<Vec<_>>(), vec![&[][..], &[][..]]);
+    }
+
+    #[test]
+    fn test_from_str() {
+        let grid: Grid<char> = "ab\ncd".parse().unwrap();
+        assert_eq!(grid.position(|&ch| ch == 'c'), Some(Coord::new(1, 0)));
+    }
+
+    #[test]
+    fn test_try_from_input_errors() {
+        assert_eq!(
+            Grid::<char>::from_str("abc\nde"),
+            Err(GridParseError::RaggedLine {
+                row: 1,
+                expected: 3,
+                found: 2
+            })
+        );
+        assert_eq!(Grid::<char>::from_str(""), Err(GridParseError::Empty));
+        assert_eq!(
+            Grid::try_from_input("12\n3x", |ch| ch.to_digit(10).ok_or(())),
+            Err(GridParseError::InvalidChar {
+                row: 1,
+                col: 1,
+                ch: 'x'
+            })
+        );
+    }
+
+    #[test]
+    fn test_neighbors() {
+        let grid = Grid::from_input("ab\ncd", |ch| ch);
+        let neighbors: Vec<_> = grid
+            .get_map_neighbors_from_coord(Coord::new(0, 0))
+            .iter()
+            .map(|n| n.map(|(_, &ch)| ch))
+            .collect();
+        assert_eq!(neighbors, vec![None, Some('c'), None, Some('b')]);
+    }
+
+    #[test]
+    fn test_all_neighbors() {
+        let grid = Grid::from_input("abc\ndef\nghi", |ch| ch);
+        let neighbors = grid.get_all_neighbors_from_coord(Coord::new(1, 1));
+        let chars: String = neighbors.iter().flatten().map(|&(_, &ch)| ch).collect();
+        assert_eq!(chars, "bcfihgda");
+        assert_eq!(
+            *neighbors.get(OrdinalDirection::DownLeft),
+            Some((Coord::new(2, 0), &'g'))
+        );
+
+        let corner = grid.get_all_neighbors_from_coord(Coord::new(0, 0));
+        assert_eq!(corner.iter().flatten().count(), 3);
+    }
+}
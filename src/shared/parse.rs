@@ -0,0 +1,325 @@
+//! Small combinators for parsing puzzle inputs.
+//!
+//! Every combinator reports where it failed as a [`ParseError`]: the column within the text it was given,
+//! and the line for combinators that work on several lines.
+use num_traits::{NumCast, PrimInt};
+
+use crate::template::ParseError;
+
+/// A position in a line of text, for parsing it piece by piece, e.g. `Button A: X+94, Y+34`:
+///
+/// ```
+/// # use advent_of_code::shared::parse::Cursor;
+/// let mut cursor = Cursor::new("Button A: X+94, Y+34");
+/// cursor.literal("Button A: X+")?;
+/// let x: i64 = cursor.integer()?;
+/// cursor.literal(", Y+")?;
+/// let y: i64 = cursor.integer()?;
+/// cursor.end()?;
+/// assert_eq!((x, y), (94, 34));
+/// # Ok::<(), advent_of_code::template::ParseError>(())
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Cursor<'a> {
+    text: &'a str,
+    offset: usize,
+}
+
+impl<'a> Cursor<'a> {
+    pub fn new(text: &'a str) -> Self {
+        Self { text, offset: 0 }
+    }
+
+    /// The text that has not been parsed yet.
+    pub fn rest(&self) -> &'a str {
+        &self.text[self.offset..]
+    }
+
+    pub fn is_at_end(&self) -> bool {
+        self.offset == self.text.len()
+    }
+
+    /// The 1-based column the cursor is at.
+    pub fn column(&self) -> usize {
+        self.text[..self.offset].chars().count() + 1
+    }
+
+    /// An error at the cursor.
+    pub fn error(&self, message: impl Into<String>) -> ParseError {
+        ParseError::new(message).at_column(self.column())
+    }
+
+    /// Describes what comes next, for error messages.
+    fn found(&self) -> String {
+        match self.rest().chars().next() {
+            Some(ch) => format!("`{ch}`"),
+            None => "the end".into(),
+        }
+    }
+
+    pub fn skip_whitespace(&mut self) {
+        self.offset = self.text.len() - self.rest().trim_start().len();
+    }
+
+    /// Skip `token` if it comes next, returning whether it did.
+    pub fn eat(&mut self, token: &str) -> bool {
+        let found = self.rest().starts_with(token);
+        if found {
+            self.offset += token.len();
+        }
+        found
+    }
+
+    /// Expect `token` to come next and skip it.
+    pub fn literal(&mut self, token: &str) -> Result<(), ParseError> {
+        if self.eat(token) {
+            Ok(())
+        } else {
+            Err(self.error(format!("expected `{token}`, found {}", self.found())))
+        }
+    }
+
+    /// Parse an integer. A leading `-` is only accepted for signed types.
+    /// Numbers that do not fit into `T` are an error, pointing at their start.
+    pub fn integer<T: PrimInt>(&mut self) -> Result<T, ParseError> {
+        let start = *self;
+        let negative = T::min_value() < T::zero() && self.eat("-");
+
+        let digits = self.rest().bytes().take_while(u8::is_ascii_digit).count();
+        if digits == 0 {
+            let error = self.error(format!("expected a number, found {}", self.found()));
+            *self = start;
+            return Err(error);
+        }
+
+        let ten = <T as NumCast>::from(10).unwrap();
+        let mut value = T::zero();
+
+        for digit in self.rest()[..digits].bytes() {
+            let digit = <T as NumCast>::from(digit - b'0').unwrap();
+            let next = if negative {
+                value.checked_mul(&ten).and_then(|v| v.checked_sub(&digit))
+            } else {
+                value.checked_mul(&ten).and_then(|v| v.checked_add(&digit))
+            };
+
+            value = next.ok_or_else(|| {
+                start.error(format!(
+                    "number does not fit into {}",
+                    std::any::type_name::<T>()
+                ))
+            })?;
+        }
+
+        self.offset += digits;
+        Ok(value)
+    }
+
+    /// Expect the whole text to have been parsed.
+    pub fn end(&self) -> Result<(), ParseError> {
+        if self.is_at_end() {
+            Ok(())
+        } else {
+            Err(self.error(format!("unexpected {}", self.found())))
+        }
+    }
+}
+
+/// Parse a whole text as an integer, signed or unsigned depending on `T`.
+pub fn integer<T: PrimInt>(text: &str) -> Result<T, ParseError> {
+    let mut cursor = Cursor::new(text);
+    let value = cursor.integer()?;
+    cursor.end()?;
+    Ok(value)
+}
+
+/// Every number in a line, skipping whatever is between them, e.g. `[94, 34]` for `Button A: X+94, Y+34`.
+/// For signed types, a `-` right before a number is its sign, so `1-3` is read as `[1, -3]`.
+pub fn numbers<T: PrimInt>(line: &str) -> Result<Vec<T>, ParseError> {
+    let signed = T::min_value() < T::zero();
+    let mut cursor = Cursor::new(line);
+    let mut numbers = vec![];
+
+    while let Some(next) = cursor.rest().find(|ch: char| ch.is_ascii_digit()) {
+        cursor.offset += next;
+        if signed && cursor.text[..cursor.offset].ends_with('-') {
+            cursor.offset -= 1;
+        }
+        numbers.push(cursor.integer()?);
+    }
+
+    Ok(numbers)
+}
+
+/// Parse the items of a list, e.g. `separated("1,2,3", ",", integer::<u64>)`.
+/// Errors point at the column in the whole text.
+pub fn separated<T, E: Into<ParseError>>(
+    text: &str,
+    separator: &str,
+    parse: impl Fn(&str) -> Result<T, E>,
+) -> Result<Vec<T>, ParseError> {
+    let mut offset = 0;
+
+    text.split(separator)
+        .map(|item| {
+            let parsed = parse(item).map_err(|e| e.into().offset_by(offset));
+            offset += item.chars().count() + separator.chars().count();
+            parsed
+        })
+        .collect()
+}
+
+/// Parse the blocks of an input that are separated by blank lines.
+/// Errors point at the line in the whole input, the first line of the block if they have none.
+pub fn blocks<T, E: Into<ParseError>>(
+    input: &str,
+    parse: impl Fn(&str) -> Result<T, E>,
+) -> Result<Vec<T>, ParseError> {
+    let mut blocks = vec![];
+    let mut block: Vec<&str> = vec![];
+    let mut first_line = 1;
+
+    // a blank line at the end finishes the last block.
+    for (index, line) in input.lines().chain([""]).enumerate() {
+        if !line.trim().is_empty() {
+            if block.is_empty() {
+                first_line = index + 1;
+            }
+            block.push(line);
+            continue;
+        }
+
+        if block.is_empty() {
+            continue;
+        }
+
+        let parsed = parse(&block.join("\n")).map_err(|e| {
+            let e = e.into();
+            let line = first_line + e.line.unwrap_or(1) - 1;
+            e.at_line(line)
+        })?;
+        blocks.push(parsed);
+        block.clear();
+    }
+
+    Ok(blocks)
+}
+
+/// Split a `key: value` line and parse its value.
+pub fn key_value<'a, T, E: Into<ParseError>>(
+    line: &'a str,
+    parse_value: impl Fn(&'a str) -> Result<T, E>,
+) -> Result<(&'a str, T), ParseError> {
+    let Some((key, value)) = line.split_once(": ") else {
+        return Err(ParseError::new("expected `key: value`").at_column(1));
+    };
+
+    // the value starts after the key and the `: ` separator.
+    let offset = key.chars().count() + 2;
+    let value = parse_value(value).map_err(|e| e.into().offset_by(offset))?;
+
+    Ok((key, value))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::template::parse_lines;
+
+    fn error<T: std::fmt::Debug>(result: Result<T, ParseError>) -> String {
+        result.unwrap_err().to_string()
+    }
+
+    #[test]
+    fn test_integer() {
+        assert_eq!(integer::<u64>("1234"), Ok(1234));
+        assert_eq!(integer::<i8>("-128"), Ok(-128));
+        assert_eq!(
+            error(integer::<u64>("")),
+            "column 1: expected a number, found the end"
+        );
+        assert_eq!(
+            error(integer::<u64>("-5")),
+            "column 1: expected a number, found `-`"
+        );
+        assert_eq!(error(integer::<u64>("12x")), "column 3: unexpected `x`");
+        assert_eq!(
+            error(integer::<u8>("256")),
+            "column 1: number does not fit into u8"
+        );
+        assert_eq!(
+            error(integer::<i8>("-129")),
+            "column 1: number does not fit into i8"
+        );
+    }
+
+    #[test]
+    fn test_cursor() {
+        let mut cursor = Cursor::new("mul(12,345)");
+        assert!(!cursor.eat("do"));
+        assert_eq!(cursor.literal("mul("), Ok(()));
+        assert_eq!(cursor.integer::<u64>(), Ok(12));
+        assert_eq!(
+            error(cursor.literal(")")),
+            "column 7: expected `)`, found `,`"
+        );
+        // a failed integer leaves the cursor where it was.
+        assert!(cursor.integer::<u64>().is_err());
+        assert_eq!(cursor.column(), 7);
+        assert_eq!(cursor.rest(), ",345)");
+    }
+
+    #[test]
+    fn test_numbers() {
+        assert_eq!(numbers::<i64>("p=0,4 v=3,-3"), Ok(vec![0, 4, 3, -3]));
+        assert_eq!(numbers::<u64>("1-3 a: abc"), Ok(vec![1, 3]));
+        assert_eq!(numbers::<u64>("no numbers"), Ok(vec![]));
+        assert_eq!(
+            error(numbers::<u8>("a 1 b 300")),
+            "column 7: number does not fit into u8"
+        );
+    }
+
+    #[test]
+    fn test_separated() {
+        assert_eq!(
+            separated("75,47,61", ",", integer::<u64>),
+            Ok(vec![75, 47, 61])
+        );
+        assert_eq!(
+            error(separated("75,4x,61", ",", integer::<u64>)),
+            "column 5: unexpected `x`"
+        );
+    }
+
+    #[test]
+    fn test_blocks() {
+        let input = "1\n2\n\n\n3\nx\n";
+        assert_eq!(
+            error(blocks(input, |block| parse_lines(block, integer::<u64>))),
+            "line 6, column 1: expected a number, found `x`"
+        );
+        let parsed = blocks("1\n2\n\n3\n", |block| parse_lines(block, integer::<u64>));
+        assert_eq!(parsed, Ok(vec![vec![1, 2], vec![3]]));
+    }
+
+    #[test]
+    fn test_key_value() {
+        assert_eq!(
+            key_value("190: 10 19", |value| separated(value, " ", integer::<u64>)),
+            Ok(("190", vec![10, 19]))
+        );
+        assert_eq!(
+            error(key_value("190: 10 1x", |value| separated(
+                value,
+                " ",
+                integer::<u64>
+            ))),
+            "column 10: unexpected `x`"
+        );
+        assert_eq!(
+            error(key_value("190 10", integer::<u64>)),
+            "column 1: expected `key: value`"
+        );
+    }
+}
@@ -0,0 +1,260 @@
+//! Connected-component labelling and region geometry for [`Grid`]s.
+use super::{CardinalDirection, Coord, Grid, OrdinalDirection, search::bfs};
+
+/// The connected regions of a grid, as produced by [`Grid::label_regions`].
+#[derive(Debug, Clone)]
+pub struct Regions {
+    /// For every cell, the id of the region it belongs to.
+    /// Ids are indexes into `regions`, assigned in row order of each region's first cell.
+    pub labels: Grid<usize>,
+    pub regions: Vec<Region>,
+}
+
+impl Regions {
+    pub fn iter(&self) -> impl Iterator<Item = &Region> {
+        self.regions.iter()
+    }
+
+    pub fn len(&self) -> usize {
+        self.regions.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.regions.is_empty()
+    }
+
+    /// Returns the region that the coordinate belongs to.
+    pub fn region_at(&self, coord: Coord) -> Option<&Region> {
+        self.labels
+            .get_from_coord(coord)
+            .and_then(|&id| self.regions.get(id))
+    }
+}
+
+/// A single connected region and its geometry.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Region {
+    pub id: usize,
+    /// Every cell of the region, in the order they were flood filled.
+    pub cells: Vec<Coord>,
+    perimeter: usize,
+    corners: usize,
+    bounding_box: BoundingBox,
+    boundary: Vec<Coord>,
+}
+
+impl Region {
+    /// The number of cells in the region.
+    pub fn area(&self) -> usize {
+        self.cells.len()
+    }
+
+    /// The number of cell edges that face another region or the outside of the grid.
+    pub fn perimeter(&self) -> usize {
+        self.perimeter
+    }
+
+    /// The number of corners of the region's outline, including the outlines of any holes.
+    pub fn corners(&self) -> usize {
+        self.corners
+    }
+
+    /// The number of straight sides of the region's outline, including the outlines of any holes.
+    /// A closed outline on a grid always has as many sides as it has corners.
+    pub fn sides(&self) -> usize {
+        self.corners
+    }
+
+    pub fn bounding_box(&self) -> BoundingBox {
+        self.bounding_box
+    }
+
+    /// The cells of the region that have at least one edge on the perimeter, in flood fill order.
+    pub fn boundary_cells(&self) -> &[Coord] {
+        &self.boundary
+    }
+}
+
+/// An inclusive box of grid coordinates.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct BoundingBox {
+    pub min: Coord,
+    pub max: Coord,
+}
+
+impl BoundingBox {
+    pub fn rows(&self) -> usize {
+        self.max.row - self.min.row + 1
+    }
+
+    pub fn cols(&self) -> usize {
+        self.max.col - self.min.col + 1
+    }
+
+    pub fn contains(&self, coord: Coord) -> bool {
+        (self.min.row..=self.max.row).contains(&coord.row)
+            && (self.min.col..=self.max.col).contains(&coord.col)
+    }
+}
+
+impl<T> Grid<T> {
+    /// Splits the grid into connected regions, where two cardinally adjacent cells
+    /// belong to the same region if `same_region` returns true for them.
+    pub fn label_regions(&self, same_region: impl Fn(&T, &T) -> bool) -> Regions {
+        // usize::MAX marks cells we have not labelled yet
+        let mut labels = self.map(|_| usize::MAX);
+        let mut cells_per_region = vec![];
+
+        for start in self.coord_iter() {
+            if labels[start] != usize::MAX {
+                continue;
+            }
+            let id = cells_per_region.len();
+            let filled = bfs(start, |&coord| {
+                self.neighbor_coords(coord)
+                    .filter(|&neighbour| same_region(&self[coord], &self[neighbour]))
+                    .collect::<Vec<_>>()
+            });
+            for &coord in &filled.order {
+                labels[coord] = id;
+            }
+            cells_per_region.push(filled.order);
+        }
+
+        let regions = cells_per_region
+            .into_iter()
+            .enumerate()
+            .map(|(id, cells)| Region::measure(id, cells, &labels))
+            .collect();
+
+        Regions { labels, regions }
+    }
+}
+
+impl Region {
+    fn measure(id: usize, cells: Vec<Coord>, labels: &Grid<usize>) -> Self {
+        let in_region = |coord: Coord, dir: OrdinalDirection| {
+            labels
+                .shift(coord, dir)
+                .is_some_and(|neighbour| labels[neighbour] == id)
+        };
+
+        let mut perimeter = 0;
+        let mut corners = 0;
+        let mut boundary = vec![];
+        let mut bounding_box = BoundingBox {
+            min: cells[0],
+            max: cells[0],
+        };
+
+        for &coord in &cells {
+            let open_edges = CardinalDirection::ALL
+                .into_iter()
+                .filter(|&dir| !in_region(coord, dir.into()))
+                .count();
+            perimeter += open_edges;
+            if open_edges > 0 {
+                boundary.push(coord);
+            }
+
+            // every corner of the outline touches exactly one cell corner, so we look at each
+            // pair of adjacent sides of the cell, e.g. up and right, and the diagonal between them
+            for side in CardinalDirection::ALL {
+                let next_side = side.turn_clockwise();
+                let diagonal = OrdinalDirection::from(side).turn_clockwise_45();
+                let side_in = in_region(coord, side.into());
+                let next_side_in = in_region(coord, next_side.into());
+                // an outer corner, where the outline turns around this cell
+                let is_convex = !side_in && !next_side_in;
+                // an inner corner, where the outline turns around the diagonal cell
+                let is_concave = side_in && next_side_in && !in_region(coord, diagonal);
+                if is_convex || is_concave {
+                    corners += 1;
+                }
+            }
+
+            bounding_box.min.row = bounding_box.min.row.min(coord.row);
+            bounding_box.min.col = bounding_box.min.col.min(coord.col);
+            bounding_box.max.row = bounding_box.max.row.max(coord.row);
+            bounding_box.max.col = bounding_box.max.col.max(coord.col);
+        }
+
+        Self {
+            id,
+            cells,
+            perimeter,
+            corners,
+            bounding_box,
+            boundary,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn label(input: &str) -> Regions {
+        Grid::from_input(input, |ch| ch).label_regions(|a, b| a == b)
+    }
+
+    #[test]
+    fn test_labels_regions() {
+        let regions = label("AAAA\nBBCD\nBBCC\nEEEC");
+        assert_eq!(regions.len(), 5);
+        assert_eq!(regions.labels[Coord::new(0, 0)], 0);
+        assert_eq!(regions.labels[Coord::new(1, 0)], 1);
+        assert_eq!(regions.labels[Coord::new(3, 3)], 2);
+        assert_eq!(regions.region_at(Coord::new(2, 2)).unwrap().area(), 4);
+    }
+
+    #[test]
+    fn test_area_perimeter_and_sides() {
+        let regions = label("AAAA\nBBCD\nBBCC\nEEEC");
+        let measured: Vec<(usize, usize, usize)> = regions
+            .iter()
+            .map(|region| (region.area(), region.perimeter(), region.sides()))
+            .collect();
+        assert_eq!(
+            measured,
+            vec![(4, 10, 4), (4, 8, 4), (4, 10, 8), (1, 4, 4), (3, 8, 4)]
+        );
+    }
+
+    #[test]
+    fn test_sides_with_holes() {
+        // the outer region has 4 outer sides and 2 * 4 sides around the holes
+        let regions = label("OOOOO\nOXOXO\nOOOOO");
+        let outer = regions.region_at(Coord::new(0, 0)).unwrap();
+        assert_eq!(outer.area(), 13);
+        assert_eq!(outer.sides(), 12);
+        assert_eq!(outer.perimeter(), 16 + 8);
+    }
+
+    #[test]
+    fn test_diagonal_regions_are_separate() {
+        let regions = label("AAAAAA\nAAABBA\nAAABBA\nABBAAA\nABBAAA\nAAAAAA");
+        let a = regions.region_at(Coord::new(0, 0)).unwrap();
+        assert_eq!(a.sides(), 12);
+        assert_eq!(regions.len(), 3);
+    }
+
+    #[test]
+    fn test_bounding_box_and_boundary() {
+        let regions = label("...\n.##\n.##");
+        let hashes = regions.region_at(Coord::new(1, 1)).unwrap();
+        assert_eq!(
+            hashes.bounding_box(),
+            BoundingBox {
+                min: Coord::new(1, 1),
+                max: Coord::new(2, 2)
+            }
+        );
+        assert_eq!(hashes.bounding_box().rows(), 2);
+        assert_eq!(hashes.boundary_cells().len(), 4);
+
+        let dots = regions.region_at(Coord::new(0, 0)).unwrap();
+        assert_eq!(dots.area(), 5);
+        assert!(dots.bounding_box().contains(Coord::new(1, 1)));
+    }
+}
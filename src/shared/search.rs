@@ -0,0 +1,427 @@
+//! Generic graph searches over any hashable node type.
+//!
+//! Nodes are discovered through a successor closure, so the same functions work for plain
+//! grid [`Coord`](super::Coord)s as well as for richer state such as `(Coord, CardinalDirection)` pairs.
+use std::{
+    cmp::Ordering,
+    collections::{BinaryHeap, HashMap, HashSet, VecDeque},
+    hash::Hash,
+    ops::Add,
+};
+
+use num_traits::Zero;
+
+/// The outcome of exploring a graph from a single start node.
+#[derive(Debug, Clone)]
+pub struct SearchResult<N, C> {
+    pub start: N,
+    /// The cost of reaching every discovered node from the start.
+    pub distances: HashMap<N, C>,
+    /// The node each discovered node was first reached from. The start has no predecessor.
+    pub predecessors: HashMap<N, N>,
+    /// Every discovered node, in the order the search visited it.
+    pub order: Vec<N>,
+}
+
+impl<N: Eq + Hash + Clone, C> SearchResult<N, C> {
+    fn new(start: N, start_cost: C) -> Self {
+        Self {
+            start: start.clone(),
+            distances: HashMap::from([(start, start_cost)]),
+            predecessors: HashMap::new(),
+            order: vec![],
+        }
+    }
+
+    pub fn distance(&self, node: &N) -> Option<&C> {
+        self.distances.get(node)
+    }
+
+    pub fn is_reached(&self, node: &N) -> bool {
+        self.distances.contains_key(node)
+    }
+
+    /// Reconstructs the path from the start to `target` by walking back through the predecessors.
+    /// Returns `None` if the target was never reached.
+    pub fn path_to(&self, target: &N) -> Option<Vec<N>> {
+        if !self.is_reached(target) {
+            return None;
+        }
+        let mut path = vec![target.clone()];
+        let mut current = target;
+        while let Some(previous) = self.predecessors.get(current) {
+            path.push(previous.clone());
+            current = previous;
+        }
+        path.reverse();
+        Some(path)
+    }
+}
+
+/// Breadth-first search from `start`, where every step costs 1.
+pub fn bfs<N, I>(start: N, mut successors: impl FnMut(&N) -> I) -> SearchResult<N, usize>
+where
+    N: Eq + Hash + Clone,
+    I: IntoIterator<Item = N>,
+{
+    let mut result = SearchResult::new(start.clone(), 0);
+    let mut queue = VecDeque::from([start]);
+
+    while let Some(node) = queue.pop_front() {
+        let distance = result.distances[&node];
+        for next in successors(&node) {
+            if result.distances.contains_key(&next) {
+                continue;
+            }
+            result.distances.insert(next.clone(), distance + 1);
+            result.predecessors.insert(next.clone(), node.clone());
+            queue.push_back(next);
+        }
+        result.order.push(node);
+    }
+
+    result
+}
+
+/// Depth-first search from `start`. Distances are the depth at which each node was first found,
+/// which is not necessarily the shortest distance.
+pub fn dfs<N, I>(start: N, mut successors: impl FnMut(&N) -> I) -> SearchResult<N, usize>
+where
+    N: Eq + Hash + Clone,
+    I: IntoIterator<Item = N>,
+{
+    let mut result = SearchResult::new(start.clone(), 0);
+    let mut stack = vec![(start, None)];
+    let mut visited = HashSet::new();
+
+    while let Some((node, from)) = stack.pop() {
+        if !visited.insert(node.clone()) {
+            continue;
+        }
+        if let Some(from) = from {
+            let depth = result.distances[&from] + 1;
+            result.distances.insert(node.clone(), depth);
+            result.predecessors.insert(node.clone(), from);
+        }
+        for next in successors(&node) {
+            if !visited.contains(&next) {
+                stack.push((next, Some(node.clone())));
+            }
+        }
+        result.order.push(node);
+    }
+
+    result
+}
+
+/// Dijkstra's algorithm from `start`, exploring the whole reachable graph.
+/// The successor closure yields `(node, step_cost)` pairs.
+pub fn dijkstra<N, C, I>(start: N, successors: impl FnMut(&N) -> I) -> SearchResult<N, C>
+where
+    N: Eq + Hash + Clone,
+    C: Copy + Ord + Add<Output = C> + Zero,
+    I: IntoIterator<Item = (N, C)>,
+{
+    best_first(start, successors, |_| C::zero(), |_| false)
+}
+
+/// A* search from `start` to the first node matching `is_goal`.
+/// The heuristic must never overestimate the remaining cost for the path to be the shortest.
+/// Returns the path, including both ends, and its total cost.
+pub fn astar<N, C, I>(
+    start: N,
+    successors: impl FnMut(&N) -> I,
+    heuristic: impl FnMut(&N) -> C,
+    mut is_goal: impl FnMut(&N) -> bool,
+) -> Option<(Vec<N>, C)>
+where
+    N: Eq + Hash + Clone,
+    C: Copy + Ord + Add<Output = C> + Zero,
+    I: IntoIterator<Item = (N, C)>,
+{
+    let mut goal = None;
+    let result = best_first(start, successors, heuristic, |node| {
+        if is_goal(node) {
+            goal = Some(node.clone());
+            true
+        } else {
+            false
+        }
+    });
+    let goal = goal?;
+    let cost = *result.distance(&goal)?;
+    Some((result.path_to(&goal)?, cost))
+}
+
+/// Shortest path search with an optional heuristic, i.e. Dijkstra when `heuristic` is `None`
+/// and A* otherwise. Stops at the first node matching `is_goal`.
+pub fn shortest_path<N, C, I>(
+    start: N,
+    successors: impl FnMut(&N) -> I,
+    heuristic: Option<&dyn Fn(&N) -> C>,
+    is_goal: impl FnMut(&N) -> bool,
+) -> Option<(Vec<N>, C)>
+where
+    N: Eq + Hash + Clone,
+    C: Copy + Ord + Add<Output = C> + Zero,
+    I: IntoIterator<Item = (N, C)>,
+{
+    match heuristic {
+        Some(heuristic) => astar(start, successors, heuristic, is_goal),
+        None => astar(start, successors, |_| C::zero(), is_goal),
+    }
+}
+
+/// Counts the distinct paths from `start` to any node matching `is_goal`.
+/// The graph must be acyclic, otherwise this will not terminate.
+pub fn count_paths<N, I>(
+    start: N,
+    mut successors: impl FnMut(&N) -> I,
+    mut is_goal: impl FnMut(&N) -> bool,
+) -> u64
+where
+    N: Eq + Hash + Clone,
+    I: IntoIterator<Item = N>,
+{
+    fn inner<N: Eq + Hash + Clone, I: IntoIterator<Item = N>>(
+        node: &N,
+        successors: &mut impl FnMut(&N) -> I,
+        is_goal: &mut impl FnMut(&N) -> bool,
+        memo: &mut HashMap<N, u64>,
+    ) -> u64 {
+        if is_goal(node) {
+            return 1;
+        }
+        if let Some(&count) = memo.get(node) {
+            return count;
+        }
+        let next_nodes: Vec<N> = successors(node).into_iter().collect();
+        let count = next_nodes
+            .iter()
+            .map(|next| inner(next, successors, is_goal, memo))
+            .sum();
+        memo.insert(node.clone(), count);
+        count
+    }
+
+    inner(&start, &mut successors, &mut is_goal, &mut HashMap::new())
+}
+
+/// Shared implementation of Dijkstra and A*. `stop` is checked whenever a node is settled.
+fn best_first<N, C, I>(
+    start: N,
+    mut successors: impl FnMut(&N) -> I,
+    mut heuristic: impl FnMut(&N) -> C,
+    mut stop: impl FnMut(&N) -> bool,
+) -> SearchResult<N, C>
+where
+    N: Eq + Hash + Clone,
+    C: Copy + Ord + Add<Output = C> + Zero,
+    I: IntoIterator<Item = (N, C)>,
+{
+    let mut result = SearchResult::new(start.clone(), C::zero());
+    let mut heap = BinaryHeap::new();
+    // the sequence number breaks ties in insertion order, so we never need `N: Ord`
+    let mut sequence = 0;
+    heap.push(QueueEntry {
+        priority: heuristic(&start),
+        sequence,
+        cost: C::zero(),
+        node: start,
+    });
+
+    while let Some(QueueEntry { cost, node, .. }) = heap.pop() {
+        // skip stale entries, we have already found a cheaper way here
+        if result.distances.get(&node).is_some_and(|&best| best < cost) {
+            continue;
+        }
+        result.order.push(node.clone());
+        if stop(&node) {
+            break;
+        }
+        for (next, step_cost) in successors(&node) {
+            let next_cost = cost + step_cost;
+            if result
+                .distances
+                .get(&next)
+                .is_some_and(|&best| best <= next_cost)
+            {
+                continue;
+            }
+            result.distances.insert(next.clone(), next_cost);
+            result.predecessors.insert(next.clone(), node.clone());
+            sequence += 1;
+            heap.push(QueueEntry {
+                priority: next_cost + heuristic(&next),
+                sequence,
+                cost: next_cost,
+                node: next,
+            });
+        }
+    }
+
+    result
+}
+
+struct QueueEntry<N, C> {
+    priority: C,
+    sequence: usize,
+    cost: C,
+    node: N,
+}
+
+impl<N, C: Ord> PartialEq for QueueEntry<N, C> {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl<N, C: Ord> Eq for QueueEntry<N, C> {}
+
+impl<N, C: Ord> PartialOrd for QueueEntry<N, C> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<N, C: Ord> Ord for QueueEntry<N, C> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        // reversed, so that the max-heap pops the cheapest entry first
+        other
+            .priority
+            .cmp(&self.priority)
+            .then_with(|| other.sequence.cmp(&self.sequence))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::shared::{CardinalDirection, Coord, Grid};
+
+    fn maze() -> Grid<char> {
+        Grid::from_input(
+            "\
+S.#.
+..#.
+.#..
+...E",
+            |ch| ch,
+        )
+    }
+
+    fn open_neighbors(grid: &Grid<char>, coord: Coord) -> Vec<Coord> {
+        grid.neighbor_coords(coord)
+            .filter(|&c| grid[c] != '#')
+            .collect()
+    }
+
+    #[test]
+    fn test_bfs_distances_and_path() {
+        let grid = maze();
+        let start = grid.position(|&ch| ch == 'S').unwrap();
+        let end = grid.position(|&ch| ch == 'E').unwrap();
+        let result = bfs(start, |&c| open_neighbors(&grid, c));
+
+        assert_eq!(result.distance(&end), Some(&6));
+        let path = result.path_to(&end).unwrap();
+        assert_eq!(path.len(), 7);
+        assert_eq!(path.first(), Some(&start));
+        assert_eq!(path.last(), Some(&end));
+        assert!(!result.is_reached(&Coord::new(0, 2)));
+    }
+
+    #[test]
+    fn test_dfs_visits_everything_reachable() {
+        let grid = maze();
+        let result = dfs(Coord::new(0, 0), |&c| open_neighbors(&grid, c));
+        assert_eq!(
+            result.order.len(),
+            grid.iter().filter(|&&ch| ch != '#').count()
+        );
+        assert_eq!(result.order[0], Coord::new(0, 0));
+    }
+
+    #[test]
+    fn test_dijkstra_weighted() {
+        // going right is expensive, going down is cheap
+        let grid = &maze();
+        let result = dijkstra(Coord::new(0, 0), |&c| {
+            [CardinalDirection::Down, CardinalDirection::Right]
+                .into_iter()
+                .filter_map(move |dir| grid.shift(c, dir).map(|n| (n, dir)))
+                .filter(|(n, _)| grid[*n] != '#')
+                .map(|(n, dir)| {
+                    (
+                        n,
+                        if dir == CardinalDirection::Right {
+                            5
+                        } else {
+                            1
+                        },
+                    )
+                })
+                .collect::<Vec<_>>()
+        });
+        assert_eq!(result.distance(&Coord::new(3, 3)), Some(&18));
+    }
+
+    #[test]
+    fn test_astar_with_state() {
+        // searching over (coord, facing) pairs, where turning costs extra
+        let grid = maze();
+        let start = (Coord::new(0, 0), CardinalDirection::Right);
+        let end = Coord::new(3, 3);
+        let (path, cost) = astar(
+            start,
+            |&(coord, dir)| {
+                let mut next = vec![
+                    ((coord, dir.turn_clockwise()), 10),
+                    ((coord, dir.turn_anti_clockwise()), 10),
+                ];
+                if let Some(forward) = grid.shift(coord, dir).filter(|&c| grid[c] != '#') {
+                    next.push(((forward, dir), 1));
+                }
+                next
+            },
+            |&(coord, _)| coord.manhattan_distance(end),
+            |&(coord, _)| coord == end,
+        )
+        .unwrap();
+        assert_eq!(path.last().unwrap().0, end);
+        assert_eq!(cost, 6 + 2 * 10);
+    }
+
+    #[test]
+    fn test_shortest_path_without_heuristic() {
+        let grid = maze();
+        let end = Coord::new(3, 3);
+        let (path, cost) = shortest_path(
+            Coord::new(0, 0),
+            |&c| open_neighbors(&grid, c).into_iter().map(|n| (n, 1_usize)),
+            None,
+            |&c| c == end,
+        )
+        .unwrap();
+        assert_eq!(cost, 6);
+        assert_eq!(path.len(), 7);
+    }
+
+    #[test]
+    fn test_count_paths() {
+        // every monotone path through a 3x3 grid
+        let grid = Grid::filled(3, 3, ());
+        let end = Coord::new(2, 2);
+        let count = count_paths(
+            Coord::new(0, 0),
+            |&c| {
+                [CardinalDirection::Down, CardinalDirection::Right]
+                    .into_iter()
+                    .filter_map(|dir| grid.shift(c, dir))
+                    .collect::<Vec<_>>()
+            },
+            |&c| c == end,
+        );
+        assert_eq!(count, 6);
+    }
+}
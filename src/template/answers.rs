@@ -0,0 +1,330 @@
+use std::{
+    collections::HashMap,
+    fmt::Display,
+    fs,
+    io::Error,
+    path::{Path, PathBuf},
+    str::FromStr,
+};
+use tinyjson::JsonValue;
+
+use crate::template::{Day, Year};
+
+static ANSWERS_FILE_NAME: &str = "answers.json";
+
+/// `data/answers.json` for the home year, `data/<year>/answers.json` for any other year.
+fn file_path(year: Year) -> PathBuf {
+    year.data_dir("./data").join(ANSWERS_FILE_NAME)
+}
+
+/// Represents the known-good answers for a single day, on your own input or a named one.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Answer {
+    pub day: Day,
+    /// The name of the input these answers belong to, `None` for your own input.
+    pub input: Option<String>,
+    pub part_1: Option<String>,
+    pub part_2: Option<String>,
+}
+
+impl Answer {
+    pub fn part(&self, part: u8) -> Option<&str> {
+        match part {
+            1 => self.part_1.as_deref(),
+            2 => self.part_2.as_deref(),
+            _ => None,
+        }
+    }
+}
+
+/// Represents the known-good answers for a set of days.
+/// Can be serialized from / to JSON.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Answers {
+    pub data: Vec<Answer>,
+}
+
+/// The outcome of checking a part's answer against the recorded one.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Verdict {
+    Pass,
+    Fail { expected: String },
+    Missing,
+}
+
+impl Display for Verdict {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Verdict::Pass => write!(f, "PASS"),
+            Verdict::Fail { expected } => write!(f, "FAIL (expected {expected})"),
+            Verdict::Missing => write!(f, "MISSING"),
+        }
+    }
+}
+
+impl Answers {
+    /// Dehydrate answers to a JSON file.
+    pub fn store_file(&self, year: Year) -> Result<(), Error> {
+        let path = file_path(year);
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let json = JsonValue::from(self.clone());
+        let mut file = fs::File::create(path)?;
+        json.format_to(&mut file)
+    }
+
+    /// Rehydrate answers from a JSON file. If not present, returns empty answers.
+    /// A file that can't be read or parsed is an error, so it is never replaced by empty answers.
+    pub fn read_from_file(year: Year) -> Result<Self, String> {
+        let path = file_path(year);
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        Self::read_from_path(&path)
+            .map_err(|e| format!("Could not read \"{}\": {e}", path.display()))
+    }
+
+    /// Rehydrate answers from a JSON file at any path.
+    pub fn read_from_path(path: &Path) -> Result<Self, String> {
+        fs::read_to_string(path)
+            .map_err(|x| x.to_string())
+            .and_then(Answers::try_from)
+    }
+
+    fn position(&self, day: Day, input: Option<&str>) -> Option<usize> {
+        self.data
+            .iter()
+            .position(|a| a.day == day && a.input.as_deref() == input)
+    }
+
+    /// The answer for a part on an input, `None` standing for your own input.
+    pub fn get(&self, day: Day, input: Option<&str>, part: u8) -> Option<&str> {
+        self.position(day, input)
+            .and_then(|index| self.data[index].part(part))
+    }
+
+    /// Record the answer for a part, overwriting any previous answer.
+    pub fn set(&mut self, day: Day, input: Option<&str>, part: u8, answer: &str) {
+        let index = match self.position(day, input) {
+            Some(index) => index,
+            None => {
+                self.data.push(Answer {
+                    day,
+                    input: input.map(Into::into),
+                    part_1: None,
+                    part_2: None,
+                });
+                self.data
+                    .sort_unstable_by(|a, b| (a.day, &a.input).cmp(&(b.day, &b.input)));
+                self.position(day, input).unwrap()
+            }
+        };
+
+        match part {
+            1 => self.data[index].part_1 = Some(answer.into()),
+            2 => self.data[index].part_2 = Some(answer.into()),
+            _ => {}
+        }
+    }
+
+    /// Check the answer a solution gave for a part, `None` if it did not produce one.
+    pub fn check(&self, day: Day, input: Option<&str>, part: u8, actual: Option<&str>) -> Verdict {
+        match (self.get(day, input, part), actual) {
+            (None, _) => Verdict::Missing,
+            (Some(expected), Some(actual)) if expected == actual => Verdict::Pass,
+            (Some(expected), _) => Verdict::Fail {
+                expected: expected.into(),
+            },
+        }
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<Answers> for JsonValue {
+    fn from(value: Answers) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert(
+            "data".into(),
+            JsonValue::Array(value.data.iter().map(JsonValue::from).collect()),
+        );
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<String> for Answers {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        let json = JsonValue::from_str(&value).or(Err("not valid JSON file."))?;
+
+        let json_data = json
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("expected JSON document to be an object.")?
+            .get("data")
+            .ok_or("expected JSON document to have key `data`.")?
+            .get::<Vec<JsonValue>>()
+            .ok_or("expected `json.data` to be an array.")?;
+
+        Ok(Answers {
+            data: json_data
+                .iter()
+                .map(Answer::try_from)
+                .collect::<Result<_, _>>()?,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<&Answer> for JsonValue {
+    fn from(value: &Answer) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert("day".into(), JsonValue::String(value.day.to_string()));
+
+        if let Some(input) = &value.input {
+            map.insert("input".into(), JsonValue::String(input.clone()));
+        }
+
+        for (key, answer) in [("part_1", &value.part_1), ("part_2", &value.part_2)] {
+            map.insert(
+                key.into(),
+                match answer {
+                    Some(x) => JsonValue::String(x.clone()),
+                    None => JsonValue::Null,
+                },
+            );
+        }
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for Answer {
+    type Error = String;
+
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected answer to be a JSON object.")?;
+
+        let day = json
+            .get("day")
+            .and_then(|v| v.get::<String>())
+            .and_then(|day| Day::from_str(day).ok())
+            .ok_or("Expected answer.day to be a Day struct.")?;
+
+        // answers without an input belong to your own input.
+        let input = match json.get("input") {
+            None => None,
+            Some(v) => Some(
+                v.get::<String>()
+                    .ok_or("Expected answer.input to be a string.")?
+                    .clone(),
+            ),
+        };
+
+        let part_1 = json
+            .get("part_1")
+            .map(|v| if v.is_null() { None } else { v.get::<String>() })
+            .ok_or("Expected answer.part_1 to be null or string.")?;
+
+        let part_2 = json
+            .get("part_2")
+            .map(|v| if v.is_null() { None } else { v.get::<String>() })
+            .ok_or("Expected answer.part_2 to be null or string.")?;
+
+        Ok(Answer {
+            day,
+            input,
+            part_1: part_1.cloned(),
+            part_2: part_2.cloned(),
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{Answers, Verdict};
+    use crate::day;
+
+    #[test]
+    fn handles_json_answers() {
+        let json = r#"{ "data": [{ "day": "01", "part_1": "1234", "part_2": null }] }"#.to_string();
+        let answers = Answers::try_from(json).unwrap();
+        assert_eq!(answers.get(day!(1), None, 1), Some("1234"));
+        assert_eq!(answers.get(day!(1), None, 2), None);
+        assert_eq!(answers.get(day!(2), None, 1), None);
+    }
+
+    #[test]
+    #[should_panic]
+    fn panics_for_malformed_answers() {
+        let json = r#"{ "data": [{ "day": "26", "part_1": null, "part_2": null }] }"#.to_string();
+        Answers::try_from(json).unwrap();
+    }
+
+    #[test]
+    fn round_trips_json() {
+        let mut answers = Answers::default();
+        answers.set(day!(3), None, 2, "multi\nline");
+        answers.set(day!(1), None, 1, "42");
+        let json = tinyjson::JsonValue::from(answers.clone())
+            .stringify()
+            .unwrap();
+        assert_eq!(Answers::try_from(json).unwrap(), answers);
+    }
+
+    #[test]
+    fn sets_answers_in_day_order() {
+        let mut answers = Answers::default();
+        answers.set(day!(5), None, 1, "5");
+        answers.set(day!(2), None, 2, "2");
+        answers.set(day!(5), None, 1, "55");
+        assert_eq!(answers.data.len(), 2);
+        assert_eq!(answers.data[0].day, day!(2));
+        assert_eq!(answers.get(day!(5), None, 1), Some("55"));
+    }
+
+    #[test]
+    fn checks_answers() {
+        let mut answers = Answers::default();
+        answers.set(day!(1), None, 1, "42");
+        assert_eq!(answers.check(day!(1), None, 1, Some("42")), Verdict::Pass);
+        assert_eq!(
+            answers.check(day!(1), None, 1, Some("41")),
+            Verdict::Fail {
+                expected: "42".into()
+            }
+        );
+        assert_eq!(
+            answers.check(day!(1), None, 1, None),
+            Verdict::Fail {
+                expected: "42".into()
+            }
+        );
+        assert_eq!(answers.check(day!(1), None, 2, Some("7")), Verdict::Missing);
+    }
+
+    #[test]
+    fn keeps_answers_per_input() {
+        let mut answers = Answers::default();
+        answers.set(day!(1), Some("alice"), 1, "7");
+        answers.set(day!(1), None, 1, "42");
+        assert_eq!(answers.data[0].input, None);
+        assert_eq!(answers.get(day!(1), None, 1), Some("42"));
+        assert_eq!(answers.get(day!(1), Some("alice"), 1), Some("7"));
+        assert_eq!(answers.get(day!(1), Some("bob"), 1), None);
+
+        let json = tinyjson::JsonValue::from(answers.clone())
+            .stringify()
+            .unwrap();
+        assert_eq!(Answers::try_from(json).unwrap(), answers);
+    }
+}
@@ -4,7 +4,10 @@ use std::{
     process::{Command, Output, Stdio},
 };
 
-use crate::template::Day;
+use crate::template::{
+    aoc_client::{AocClient, AocClientError, SubmitOutcome},
+    Puzzle,
+};
 
 #[derive(Debug)]
 pub enum AocCommandError {
@@ -25,94 +28,127 @@ impl Display for AocCommandError {
     }
 }
 
-pub fn check() -> Result<(), AocCommandError> {
-    Command::new("aoc")
-        .arg("-V")
-        .output()
-        .map_err(|_| AocCommandError::CommandNotFound)?;
-    Ok(())
-}
+/// The real Advent of Code, reached through aoc-cli.
+pub struct AocCli;
 
-pub fn read(day: Day) -> Result<Output, AocCommandError> {
-    let puzzle_path = get_puzzle_path(day);
+impl AocClient for AocCli {
+    fn name(&self) -> &'static str {
+        "aoc-cli"
+    }
 
-    let args = build_args(
-        "read",
-        &[
-            "--description-only".into(),
-            "--puzzle-file".into(),
-            puzzle_path,
-        ],
-        day,
-    );
+    fn check(&self) -> Result<(), AocClientError> {
+        Command::new("aoc")
+            .arg("-V")
+            .output()
+            .map_err(|_| {
+                AocClientError::Unavailable(
+                    "command \"aoc\" not found or not callable. Try running \"cargo install aoc-cli\" to install it.".into(),
+                )
+            })?;
+        Ok(())
+    }
 
-    call_aoc_cli(&args)
-}
+    fn read(&self, puzzle: Puzzle) -> Result<(), AocClientError> {
+        let puzzle_path = get_puzzle_path(puzzle);
+
+        let args = build_args(
+            "read",
+            &[
+                "--description-only".into(),
+                "--puzzle-file".into(),
+                puzzle_path,
+            ],
+            puzzle,
+        );
+
+        call_aoc_cli(&args, Stdio::inherit())?;
+        Ok(())
+    }
 
-pub fn download(day: Day) -> Result<Output, AocCommandError> {
-    let input_path = get_input_path(day);
-    let puzzle_path = get_puzzle_path(day);
-
-    let args = build_args(
-        "download",
-        &[
-            "--overwrite".into(),
-            "--input-file".into(),
-            input_path.to_string(),
-            "--puzzle-file".into(),
-            puzzle_path.to_string(),
-        ],
-        day,
-    );
-
-    let output = call_aoc_cli(&args)?;
-    println!("---");
-    println!("🎄 Successfully wrote input to \"{}\".", &input_path);
-    println!("🎄 Successfully wrote puzzle to \"{}\".", &puzzle_path);
-    Ok(output)
-}
+    fn download(&self, puzzle: Puzzle) -> Result<(), AocClientError> {
+        let input_path = get_input_path(puzzle);
+        let puzzle_path = get_puzzle_path(puzzle);
+
+        let args = build_args(
+            "download",
+            &[
+                "--overwrite".into(),
+                "--input-file".into(),
+                input_path.to_string(),
+                "--puzzle-file".into(),
+                puzzle_path.to_string(),
+            ],
+            puzzle,
+        );
+
+        call_aoc_cli(&args, Stdio::inherit())?;
+        println!("---");
+        println!("🎄 Successfully wrote input to \"{}\".", &input_path);
+        println!("🎄 Successfully wrote puzzle to \"{}\".", &puzzle_path);
+        Ok(())
+    }
 
-pub fn submit(day: Day, part: u8, result: &str) -> Result<Output, AocCommandError> {
-    // workaround: the argument order is inverted for submit.
-    let mut args = build_args("submit", &[], day);
-    args.push(part.to_string());
-    args.push(result.to_string());
-    call_aoc_cli(&args)
+    fn submit(
+        &self,
+        puzzle: Puzzle,
+        part: u8,
+        answer: &str,
+    ) -> Result<SubmitOutcome, AocClientError> {
+        // workaround: the argument order is inverted for submit.
+        let mut args = build_args("submit", &[], puzzle);
+        args.push(part.to_string());
+        args.push(answer.to_string());
+
+        // capture the reply to tell what the site said, and pass it on to the user.
+        let output = call_aoc_cli(&args, Stdio::piped())?;
+        let reply = String::from_utf8_lossy(&output.stdout);
+        eprint!("{reply}");
+        Ok(SubmitOutcome::from_reply(&reply))
+    }
 }
 
-fn get_input_path(day: Day) -> String {
-    format!("data/inputs/{day}.txt")
+impl From<AocCommandError> for AocClientError {
+    fn from(value: AocCommandError) -> Self {
+        match value {
+            AocCommandError::CommandNotFound => AocClientError::Unavailable(value.to_string()),
+            _ => AocClientError::Failed(value.to_string()),
+        }
+    }
 }
 
-fn get_puzzle_path(day: Day) -> String {
-    format!("data/puzzles/{day}.md")
+fn get_input_path(puzzle: Puzzle) -> String {
+    puzzle
+        .data_file("data", "inputs", "txt")
+        .display()
+        .to_string()
 }
 
-fn get_year() -> Option<u16> {
-    match std::env::var("AOC_YEAR") {
-        Ok(x) => x.parse().ok().or(None),
-        Err(_) => None,
-    }
+fn get_puzzle_path(puzzle: Puzzle) -> String {
+    puzzle
+        .data_file("data", "puzzles", "md")
+        .display()
+        .to_string()
 }
 
-fn build_args(command: &str, args: &[String], day: Day) -> Vec<String> {
+fn build_args(command: &str, args: &[String], puzzle: Puzzle) -> Vec<String> {
     let mut cmd_args = args.to_vec();
 
-    if let Some(year) = get_year() {
-        cmd_args.push("--year".into());
-        cmd_args.push(year.to_string());
-    }
-
-    cmd_args.append(&mut vec!["--day".into(), day.to_string(), command.into()]);
+    cmd_args.append(&mut vec![
+        "--year".into(),
+        puzzle.year.to_string(),
+        "--day".into(),
+        puzzle.day.to_string(),
+        command.into(),
+    ]);
 
     cmd_args
 }
 
-fn call_aoc_cli(args: &[String]) -> Result<Output, AocCommandError> {
+fn call_aoc_cli(args: &[String], stdout: Stdio) -> Result<Output, AocCommandError> {
     // println!("Calling >aoc with: {}", args.join(" "));
     let output = Command::new("aoc")
         .args(args)
-        .stdout(Stdio::inherit())
+        .stdout(stdout)
         .stderr(Stdio::inherit())
         .output()
         .map_err(|_| AocCommandError::CommandNotCallable)?;
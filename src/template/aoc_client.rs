@@ -0,0 +1,196 @@
+/// Abstraction over the ways of talking to Advent of Code: downloading inputs, reading puzzles and submitting answers.
+use std::{env, fmt::Display, fs, path::Path};
+
+use crate::template::{aoc_cli::AocCli, aoc_http::AocHttp, fake_aoc::FakeAoc, Puzzle};
+
+/// When set, [`client`] uses a [`FakeAoc`] serving the fixtures in this directory.
+pub const FAKE_DIR_ENV: &str = "AOC_FAKE_DIR";
+
+/// Set to `aoc-cli` to make [`client`] use the external aoc-cli instead of the built-in [`AocHttp`].
+pub const CLIENT_ENV: &str = "AOC_CLIENT";
+
+pub trait AocClient {
+    /// A short name to show in messages, e.g. "aoc-cli".
+    fn name(&self) -> &'static str;
+
+    /// Check that the client can be used, e.g. that its binary is installed.
+    fn check(&self) -> Result<(), AocClientError>;
+
+    /// Print the description of a puzzle.
+    fn read(&self, puzzle: Puzzle) -> Result<(), AocClientError>;
+
+    /// Save the input to `data/inputs/NN.txt` and the description to `data/puzzles/NN.md`,
+    /// or to `data/<year>/...` for a year other than the home year.
+    fn download(&self, puzzle: Puzzle) -> Result<(), AocClientError>;
+
+    /// Submit an answer for a part and report what the site replied.
+    /// The reply is printed to stderr, so that stdout only has a solution's results.
+    fn submit(
+        &self,
+        puzzle: Puzzle,
+        part: u8,
+        answer: &str,
+    ) -> Result<SubmitOutcome, AocClientError>;
+}
+
+/// Returns the client to use, based on the environment.
+pub fn client() -> Box<dyn AocClient> {
+    if let Ok(dir) = env::var(FAKE_DIR_ENV)
+        && !dir.is_empty()
+    {
+        return Box::new(FakeAoc::new(dir));
+    }
+
+    match env::var(CLIENT_ENV).as_deref() {
+        Ok("aoc-cli") => Box::new(AocCli),
+        _ => Box::new(AocHttp::from_env()),
+    }
+}
+
+/// Write a file, creating its parent directories if needed.
+pub(crate) fn write_creating_dirs(path: &Path, contents: &str) -> Result<(), AocClientError> {
+    path.parent()
+        .map_or(Ok(()), fs::create_dir_all)
+        .and_then(|()| fs::write(path, contents))
+        .map_err(|e| AocClientError::Failed(format!("could not write \"{}\": {e}", path.display())))
+}
+
+#[derive(Debug)]
+pub enum AocClientError {
+    /// The client can not be used, e.g. because it is not installed. Contains a hint on how to fix it.
+    Unavailable(String),
+    /// The client was reached, but the request failed.
+    Failed(String),
+}
+
+impl Display for AocClientError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            AocClientError::Unavailable(hint) => write!(f, "{hint}"),
+            AocClientError::Failed(reason) => write!(f, "request failed: {reason}"),
+        }
+    }
+}
+
+/// Whether a wrong answer was too high or too low, if the site said so.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum AnswerHint {
+    TooHigh,
+    TooLow,
+}
+
+/// What the site replied to a submission.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum SubmitOutcome {
+    Correct,
+    Incorrect {
+        hint: Option<AnswerHint>,
+    },
+    /// An answer was submitted too recently. Contains the time left to wait, if the site said.
+    Wait {
+        seconds: Option<u64>,
+    },
+    /// The part was already solved, or is not unlocked yet.
+    WrongLevel,
+    /// The reply did not match any known message.
+    Unknown(String),
+}
+
+impl SubmitOutcome {
+    /// Interpret the text of the site's reply to a submission.
+    pub fn from_reply(reply: &str) -> Self {
+        if reply.contains("That's the right answer") {
+            SubmitOutcome::Correct
+        } else if reply.contains("That's not the right answer") {
+            let hint = if reply.contains("too high") {
+                Some(AnswerHint::TooHigh)
+            } else if reply.contains("too low") {
+                Some(AnswerHint::TooLow)
+            } else {
+                None
+            };
+            SubmitOutcome::Incorrect { hint }
+        } else if reply.contains("You gave an answer too recently") {
+            SubmitOutcome::Wait {
+                seconds: parse_wait_time(reply),
+            }
+        } else if reply.contains("You don't seem to be solving the right level") {
+            SubmitOutcome::WrongLevel
+        } else {
+            SubmitOutcome::Unknown(reply.trim().to_string())
+        }
+    }
+}
+
+/// Parse the time left from e.g. "You have 1m 5s left to wait."
+fn parse_wait_time(reply: &str) -> Option<u64> {
+    let time = reply
+        .split("You have ")
+        .nth(1)?
+        .split(" left to wait")
+        .next()?;
+
+    time.split_whitespace()
+        .map(|part| {
+            let (value, unit) = part.split_at(part.find(|c: char| !c.is_ascii_digit())?);
+            let value: u64 = value.parse().ok()?;
+            match unit {
+                "s" => Some(value),
+                "m" => Some(value * 60),
+                "h" => Some(value * 60 * 60),
+                _ => None,
+            }
+        })
+        .sum()
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{AnswerHint, SubmitOutcome};
+
+    #[test]
+    fn parses_replies() {
+        assert_eq!(
+            SubmitOutcome::from_reply("That's the right answer! You are one gold star closer."),
+            SubmitOutcome::Correct
+        );
+        assert_eq!(
+            SubmitOutcome::from_reply("That's not the right answer; your answer is too high."),
+            SubmitOutcome::Incorrect {
+                hint: Some(AnswerHint::TooHigh)
+            }
+        );
+        assert_eq!(
+            SubmitOutcome::from_reply("That's not the right answer; your answer is too low."),
+            SubmitOutcome::Incorrect {
+                hint: Some(AnswerHint::TooLow)
+            }
+        );
+        assert_eq!(
+            SubmitOutcome::from_reply("That's not the right answer. If you're stuck, ..."),
+            SubmitOutcome::Incorrect { hint: None }
+        );
+        assert_eq!(
+            SubmitOutcome::from_reply(
+                "You don't seem to be solving the right level.  Did you already complete it?"
+            ),
+            SubmitOutcome::WrongLevel
+        );
+        assert_eq!(
+            SubmitOutcome::from_reply("something else"),
+            SubmitOutcome::Unknown("something else".into())
+        );
+    }
+
+    #[test]
+    fn parses_wait_times() {
+        assert_eq!(
+            SubmitOutcome::from_reply("You gave an answer too recently; you have to wait after submitting an answer before trying again.  You have 1m 5s left to wait."),
+            SubmitOutcome::Wait { seconds: Some(65) }
+        );
+        assert_eq!(
+            SubmitOutcome::from_reply("You gave an answer too recently."),
+            SubmitOutcome::Wait { seconds: None }
+        );
+    }
+}
@@ -0,0 +1,429 @@
+/// A built-in client for the Advent of Code website, so aoc-cli does not need to be installed.
+use std::{
+    env, fs,
+    path::PathBuf,
+    thread,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use ureq::{http::Response, Agent, Body};
+
+use crate::template::{
+    aoc_client::{write_creating_dirs, AocClient, AocClientError, SubmitOutcome},
+    markdown::{puzzle_to_markdown, reply_to_markdown},
+    Puzzle,
+};
+
+/// The session cookie to authenticate with. Takes precedence over the session file.
+pub const SESSION_ENV: &str = "AOC_SESSION";
+
+/// Where to send requests instead of the site, e.g. a local stand-in server.
+pub const BASE_URL_ENV: &str = "AOC_BASE_URL";
+
+/// The file in the home directory holding the session cookie, shared with aoc-cli.
+const SESSION_FILE: &str = ".adventofcode.session";
+
+const DEFAULT_BASE_URL: &str = "https://adventofcode.com";
+const USER_AGENT: &str = "github.com/fspoettel/advent-of-code-rust";
+
+/// The minimum time between two requests to the site.
+const DEFAULT_MIN_INTERVAL: Duration = Duration::from_secs(2);
+
+/// Talks to the site over HTTP. Inputs are cached on disk, so they are only downloaded once, and requests
+/// are spaced out by a minimum interval, also across runs. Puzzle pages change once a part is solved,
+/// so they are always fetched.
+pub struct AocHttp {
+    agent: Agent,
+    base_url: String,
+    session: Option<String>,
+    data_dir: PathBuf,
+    cache_dir: PathBuf,
+    min_interval: Duration,
+}
+
+impl AocHttp {
+    pub fn new(base_url: impl Into<String>, session: Option<String>) -> Self {
+        let agent = Agent::config_builder()
+            .http_status_as_error(false)
+            .timeout_global(Some(Duration::from_secs(30)))
+            .user_agent(USER_AGENT)
+            .build()
+            .into();
+
+        Self {
+            agent,
+            base_url: base_url.into().trim_end_matches('/').to_string(),
+            session,
+            data_dir: PathBuf::from("data"),
+            cache_dir: PathBuf::from("data").join(".cache"),
+            min_interval: DEFAULT_MIN_INTERVAL,
+        }
+    }
+
+    /// Configure the client from `AOC_SESSION` or `~/.adventofcode.session`, and `AOC_BASE_URL`.
+    pub fn from_env() -> Self {
+        let base_url = env::var(BASE_URL_ENV).unwrap_or_else(|_| DEFAULT_BASE_URL.into());
+        Self::new(base_url, read_session())
+    }
+
+    /// Set where downloads are written to, `data` by default.
+    #[must_use]
+    pub fn with_data_dir(mut self, data_dir: impl Into<PathBuf>) -> Self {
+        self.data_dir = data_dir.into();
+        self
+    }
+
+    /// Set where responses are cached, `data/.cache` by default.
+    #[must_use]
+    pub fn with_cache_dir(mut self, cache_dir: impl Into<PathBuf>) -> Self {
+        self.cache_dir = cache_dir.into();
+        self
+    }
+
+    /// Set the minimum time between two requests.
+    #[must_use]
+    pub fn with_min_interval(mut self, min_interval: Duration) -> Self {
+        self.min_interval = min_interval;
+        self
+    }
+
+    fn session(&self) -> Result<&str, AocClientError> {
+        self.session.as_deref().ok_or_else(|| {
+            AocClientError::Unavailable(format!(
+                "no session cookie found. Set \"{SESSION_ENV}\" or paste it into \"~/{SESSION_FILE}\"."
+            ))
+        })
+    }
+
+    fn day_path(puzzle: Puzzle) -> String {
+        format!("/{}/day/{}", puzzle.year, puzzle.day.into_inner())
+    }
+
+    fn cache_path(&self, puzzle: Puzzle, file: &str) -> PathBuf {
+        self.cache_dir
+            .join(puzzle.year.to_string())
+            .join(puzzle.day.to_string())
+            .join(file)
+    }
+
+    /// Fetch a page.
+    fn get(&self, path: &str) -> Result<String, AocClientError> {
+        let url = format!("{}{path}", self.base_url);
+        self.throttle();
+        let response = self
+            .agent
+            .get(&url)
+            .header("Cookie", format!("session={}", self.session()?))
+            .call();
+
+        read_body(&url, response)
+    }
+
+    /// Fetch a page, from the cache if it was fetched before.
+    fn get_cached(&self, path: &str, cache_path: PathBuf) -> Result<String, AocClientError> {
+        if let Ok(cached) = fs::read_to_string(&cache_path) {
+            return Ok(cached);
+        }
+
+        let body = self.get(path)?;
+        write_creating_dirs(&cache_path, &body)?;
+        Ok(body)
+    }
+
+    fn puzzle_markdown(&self, puzzle: Puzzle) -> Result<String, AocClientError> {
+        let html = self.get(&Self::day_path(puzzle))?;
+        Ok(puzzle_to_markdown(&html))
+    }
+
+    /// Wait until the minimum interval since the last request has passed, then note the time of this request.
+    fn throttle(&self) {
+        let last_request_path = self.cache_dir.join("last_request");
+
+        let last_request: Option<u128> = fs::read_to_string(&last_request_path)
+            .ok()
+            .and_then(|millis| millis.trim().parse().ok());
+
+        if let Some(last_request) = last_request {
+            let next_request = last_request + self.min_interval.as_millis();
+            if let Some(wait) = next_request.checked_sub(unix_millis()) {
+                #[allow(clippy::cast_possible_truncation)]
+                thread::sleep(Duration::from_millis(wait as u64));
+            }
+        }
+
+        let _ = write_creating_dirs(&last_request_path, &unix_millis().to_string());
+    }
+}
+
+impl AocClient for AocHttp {
+    fn name(&self) -> &'static str {
+        "adventofcode.com"
+    }
+
+    fn check(&self) -> Result<(), AocClientError> {
+        self.session()?;
+        Ok(())
+    }
+
+    fn read(&self, puzzle: Puzzle) -> Result<(), AocClientError> {
+        println!("{}", self.puzzle_markdown(puzzle)?);
+        Ok(())
+    }
+
+    fn download(&self, puzzle: Puzzle) -> Result<(), AocClientError> {
+        let input = self.get_cached(
+            &format!("{}/input", Self::day_path(puzzle)),
+            self.cache_path(puzzle, "input.txt"),
+        )?;
+        let description = self.puzzle_markdown(puzzle)?;
+
+        let input_path = puzzle.data_file(&self.data_dir, "inputs", "txt");
+        let puzzle_path = puzzle.data_file(&self.data_dir, "puzzles", "md");
+        write_creating_dirs(&input_path, &input)?;
+        write_creating_dirs(&puzzle_path, &description)?;
+
+        println!(
+            "🎄 Successfully wrote input to \"{}\".",
+            input_path.display()
+        );
+        println!(
+            "🎄 Successfully wrote puzzle to \"{}\".",
+            puzzle_path.display()
+        );
+        Ok(())
+    }
+
+    fn submit(
+        &self,
+        puzzle: Puzzle,
+        part: u8,
+        answer: &str,
+    ) -> Result<SubmitOutcome, AocClientError> {
+        let url = format!("{}{}/answer", self.base_url, Self::day_path(puzzle));
+        self.throttle();
+        let response = self
+            .agent
+            .post(&url)
+            .header("Cookie", format!("session={}", self.session()?))
+            .send_form([("level", part.to_string().as_str()), ("answer", answer)]);
+
+        let reply = reply_to_markdown(&read_body(&url, response)?);
+        eprintln!("{reply}");
+
+        Ok(SubmitOutcome::from_reply(&reply))
+    }
+}
+
+fn read_body(
+    url: &str,
+    response: Result<Response<Body>, ureq::Error>,
+) -> Result<String, AocClientError> {
+    let mut response =
+        response.map_err(|e| AocClientError::Failed(format!("could not reach {url}: {e}")))?;
+
+    match response.status().as_u16() {
+        200 => response
+            .body_mut()
+            .read_to_string()
+            .map_err(|e| AocClientError::Failed(format!("could not read {url}: {e}"))),
+        404 => Err(AocClientError::Failed(format!(
+            "{url} was not found. Is the puzzle unlocked yet?"
+        ))),
+        400 | 401 | 403 | 500 => Err(AocClientError::Failed(format!(
+            "{url} replied with status {}. Is the session cookie still valid?",
+            response.status()
+        ))),
+        _ => Err(AocClientError::Failed(format!(
+            "{url} replied with status {}.",
+            response.status()
+        ))),
+    }
+}
+
+fn read_session() -> Option<String> {
+    let session = env::var(SESSION_ENV).ok().or_else(|| {
+        let home = env::var("HOME").or_else(|_| env::var("USERPROFILE")).ok()?;
+        fs::read_to_string(PathBuf::from(home).join(SESSION_FILE)).ok()
+    })?;
+
+    let session = session.trim();
+    (!session.is_empty()).then(|| session.to_string())
+}
+
+fn unix_millis() -> u128 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |d| d.as_millis())
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::{
+        env, fs,
+        io::{BufRead, BufReader, Read, Write},
+        net::TcpListener,
+        path::{Path, PathBuf},
+        process,
+        thread::{self, JoinHandle},
+        time::{Duration, Instant},
+    };
+
+    use super::AocHttp;
+    use crate::{
+        day,
+        template::{
+            aoc_client::{AnswerHint, AocClient, SubmitOutcome},
+            Year,
+        },
+    };
+
+    /// A local stand-in for the site: answers each connection with the next canned `(status, body)`,
+    /// and hands back the requests it received once all responses are used up.
+    fn stand_in(responses: Vec<(u16, &'static str)>) -> (String, JoinHandle<Vec<String>>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let base_url = format!("http://{}", listener.local_addr().unwrap());
+
+        let handle = thread::spawn(move || {
+            responses
+                .into_iter()
+                .map(|(status, body)| {
+                    let (mut stream, _) = listener.accept().unwrap();
+                    let mut reader = BufReader::new(stream.try_clone().unwrap());
+
+                    let mut request = String::new();
+                    let mut content_length = 0;
+                    loop {
+                        let mut line = String::new();
+                        reader.read_line(&mut line).unwrap();
+                        if let Some(length) = line.to_lowercase().strip_prefix("content-length:") {
+                            content_length = length.trim().parse().unwrap();
+                        }
+                        request.push_str(&line);
+                        if line == "\r\n" {
+                            break;
+                        }
+                    }
+                    let mut body_bytes = vec![0; content_length];
+                    reader.read_exact(&mut body_bytes).unwrap();
+                    request.push_str(&String::from_utf8(body_bytes).unwrap());
+
+                    write!(
+                        stream,
+                        "HTTP/1.1 {status} Stand-in\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+                        body.len()
+                    )
+                    .unwrap();
+                    request
+                })
+                .collect()
+        });
+
+        (base_url, handle)
+    }
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir = env::temp_dir().join(format!("aoc-http-{name}-{}", process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        dir
+    }
+
+    fn client(base_url: &str, dir: &Path) -> AocHttp {
+        AocHttp::new(base_url, Some("abc".into()))
+            .with_data_dir(dir.join("data"))
+            .with_cache_dir(dir.join("cache"))
+            .with_min_interval(Duration::ZERO)
+    }
+
+    #[test]
+    fn downloads_and_caches() {
+        let dir = temp_dir("download");
+        let (base_url, server) = stand_in(vec![
+            (200, "1 2\n"),
+            (
+                200,
+                "<article class=\"day-desc\"><h2>--- Day 1 ---</h2></article>",
+            ),
+            // part one was solved elsewhere in the meantime, so part two shows up.
+            (
+                200,
+                "<article class=\"day-desc\"><h2>--- Day 1 ---</h2></article>\
+                 <article class=\"day-desc\"><h2 id=\"part2\">--- Part Two ---</h2></article>",
+            ),
+        ]);
+        let aoc = client(&base_url, &dir).with_min_interval(Duration::from_millis(100));
+
+        let timer = Instant::now();
+        aoc.download(day!(1).into()).unwrap();
+        assert!(timer.elapsed() >= Duration::from_millis(100));
+
+        let data = dir.join("data");
+        assert_eq!(
+            fs::read_to_string(data.join("puzzles").join("01.md")).unwrap(),
+            "## --- Day 1 ---\n"
+        );
+
+        // the input is cached now, only the puzzle page is fetched again.
+        aoc.download(day!(1).into()).unwrap();
+
+        let year = Year::home();
+        let requests = server.join().unwrap();
+        assert_eq!(requests.len(), 3);
+        assert!(requests[0].starts_with(&format!("GET /{year}/day/1/input ")));
+        assert!(requests[0].contains("session=abc"));
+        assert!(requests[1].starts_with(&format!("GET /{year}/day/1 ")));
+        assert!(requests[2].starts_with(&format!("GET /{year}/day/1 ")));
+
+        assert_eq!(
+            fs::read_to_string(data.join("inputs").join("01.txt")).unwrap(),
+            "1 2\n"
+        );
+        assert!(fs::read_to_string(data.join("puzzles").join("01.md"))
+            .unwrap()
+            .contains("--- Part Two ---"));
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn submits_answers() {
+        let dir = temp_dir("submit");
+        let (base_url, server) = stand_in(vec![(
+            200,
+            "<main><article><p>That's not the right answer; your answer is too low.</p></article></main>",
+        )]);
+        let aoc = client(&base_url, &dir);
+
+        assert_eq!(
+            aoc.submit(day!(3).into(), 2, "42").unwrap(),
+            SubmitOutcome::Incorrect {
+                hint: Some(AnswerHint::TooLow)
+            }
+        );
+
+        let requests = server.join().unwrap();
+        assert!(requests[0].starts_with(&format!("POST /{}/day/3/answer ", Year::home())));
+        assert!(requests[0].ends_with("level=2&answer=42"));
+
+        let _ = fs::remove_dir_all(dir);
+    }
+
+    #[test]
+    fn reports_locked_puzzles() {
+        let dir = temp_dir("locked");
+        let (base_url, server) = stand_in(vec![(404, "Not Found")]);
+        let aoc = client(&base_url, &dir);
+
+        assert!(aoc.read(day!(25).into()).is_err());
+        server.join().unwrap();
+
+        let _ = fs::remove_dir_all(dir);
+    }
+
+    #[test]
+    fn requires_session() {
+        assert!(AocHttp::new("http://localhost", None).check().is_err());
+        assert!(AocHttp::new("http://localhost", Some("abc".into()))
+            .check()
+            .is_ok());
+    }
+}
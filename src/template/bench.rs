@@ -0,0 +1,254 @@
+/// Benchmark configuration and summary statistics for `--time` runs.
+use std::{collections::HashMap, env, process, time::Duration};
+use tinyjson::JsonValue;
+
+/// The fewest samples a benchmark takes, however long a single run is.
+pub const MIN_SAMPLES: u128 = 10;
+
+const DEFAULT_BUDGET: Duration = Duration::from_secs(1);
+const DEFAULT_MAX_SAMPLES: u128 = 10_000;
+
+/// Controls how long a benchmark runs for.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct BenchConfig {
+    /// The time spent sampling a part. A tenth of this is spent on warm-up beforehand.
+    pub budget: Duration,
+    /// Sampling stops early once this many samples are taken.
+    pub max_samples: u128,
+}
+
+impl Default for BenchConfig {
+    fn default() -> Self {
+        Self {
+            budget: DEFAULT_BUDGET,
+            max_samples: DEFAULT_MAX_SAMPLES,
+        }
+    }
+}
+
+impl BenchConfig {
+    /// The time spent running a part before any samples are taken.
+    pub fn warmup(&self) -> Duration {
+        self.budget / 10
+    }
+
+    /// Parse the `--budget <ms>` and `--max-samples <n>` arguments passed to a solution binary.
+    pub fn from_args() -> Self {
+        let args: Vec<String> = env::args().collect();
+        let mut config = Self::default();
+
+        let value_of = |flag: &str| {
+            let index = args.iter().position(|x| x == flag)?;
+            let Some(value) = args.get(index + 1).and_then(|x| x.parse::<u64>().ok()) else {
+                eprintln!("Unexpected command-line input. Format: {flag} <number>");
+                process::exit(1);
+            };
+            Some(value)
+        };
+
+        if let Some(budget_millis) = value_of("--budget") {
+            config.budget = Duration::from_millis(budget_millis);
+        }
+
+        if let Some(max_samples) = value_of("--max-samples") {
+            config.max_samples = u128::from(max_samples).max(MIN_SAMPLES);
+        }
+
+        config
+    }
+
+    /// The arguments that pass this config on to a solution binary.
+    pub fn to_args(&self) -> Vec<String> {
+        vec![
+            "--budget".into(),
+            self.budget.as_millis().to_string(),
+            "--max-samples".into(),
+            self.max_samples.to_string(),
+        ]
+    }
+}
+
+/// Summary statistics over the samples of a benchmark.
+#[derive(Clone, Debug, PartialEq)]
+pub struct BenchStats {
+    pub samples: u128,
+    pub mean: Duration,
+    pub min: Duration,
+    pub median: Duration,
+    pub p95: Duration,
+    pub max: Duration,
+    pub std_dev: Duration,
+    /// Samples outside of 1.5 times the interquartile range, e.g. runs hit by a context switch.
+    pub outliers: u128,
+}
+
+impl BenchStats {
+    /// Summarize a set of samples. Returns `None` if there are no samples.
+    pub fn from_samples(samples: &[Duration]) -> Option<Self> {
+        if samples.is_empty() {
+            return None;
+        }
+
+        #[allow(clippy::cast_precision_loss)]
+        let mut nanos: Vec<f64> = samples.iter().map(|x| x.as_nanos() as f64).collect();
+        nanos.sort_unstable_by(f64::total_cmp);
+
+        #[allow(clippy::cast_precision_loss)]
+        let count = nanos.len() as f64;
+        let mean = nanos.iter().sum::<f64>() / count;
+        let variance = nanos.iter().map(|x| (x - mean).powi(2)).sum::<f64>() / count;
+
+        let median = if nanos.len().is_multiple_of(2) {
+            (nanos[nanos.len() / 2 - 1] + nanos[nanos.len() / 2]) / 2_f64
+        } else {
+            nanos[nanos.len() / 2]
+        };
+
+        let q1 = percentile(&nanos, 0.25);
+        let q3 = percentile(&nanos, 0.75);
+        let fence = (q3 - q1) * 1.5;
+        let outliers = nanos
+            .iter()
+            .filter(|&&x| x < q1 - fence || x > q3 + fence)
+            .count();
+
+        Some(Self {
+            samples: samples.len() as u128,
+            mean: to_duration(mean),
+            min: to_duration(nanos[0]),
+            median: to_duration(median),
+            p95: to_duration(percentile(&nanos, 0.95)),
+            max: to_duration(nanos[nanos.len() - 1]),
+            std_dev: to_duration(variance.sqrt()),
+            outliers: outliers as u128,
+        })
+    }
+}
+
+/// Nearest-rank percentile of sorted, non-empty values.
+#[allow(
+    clippy::cast_possible_truncation,
+    clippy::cast_precision_loss,
+    clippy::cast_sign_loss
+)]
+fn percentile(sorted: &[f64], p: f64) -> f64 {
+    let rank = (p * sorted.len() as f64).ceil() as usize;
+    sorted[rank.clamp(1, sorted.len()) - 1]
+}
+
+#[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+fn to_duration(nanos: f64) -> Duration {
+    Duration::from_nanos(nanos.round() as u64)
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<&BenchStats> for JsonValue {
+    #[allow(clippy::cast_precision_loss)]
+    fn from(value: &BenchStats) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert("samples".into(), JsonValue::Number(value.samples as f64));
+        map.insert("outliers".into(), JsonValue::Number(value.outliers as f64));
+
+        for (key, duration) in [
+            ("mean_nanos", value.mean),
+            ("min_nanos", value.min),
+            ("median_nanos", value.median),
+            ("p95_nanos", value.p95),
+            ("max_nanos", value.max),
+            ("std_dev_nanos", value.std_dev),
+        ] {
+            map.insert(key.into(), JsonValue::Number(duration.as_nanos() as f64));
+        }
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for BenchStats {
+    type Error = String;
+
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected stats to be a JSON object.")?;
+
+        let number = |key: &str| {
+            json.get(key)
+                .and_then(|v| v.get::<f64>().copied())
+                .filter(|n| *n >= 0_f64)
+                .ok_or(format!("Expected stats.{key} to be a positive number."))
+        };
+        let duration = |key: &str| number(key).map(to_duration);
+
+        Ok(BenchStats {
+            samples: number("samples")? as u128,
+            mean: duration("mean_nanos")?,
+            min: duration("min_nanos")?,
+            median: duration("median_nanos")?,
+            p95: duration("p95_nanos")?,
+            max: duration("max_nanos")?,
+            std_dev: duration("std_dev_nanos")?,
+            outliers: number("outliers")? as u128,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::time::Duration;
+    use tinyjson::JsonValue;
+
+    use super::BenchStats;
+
+    fn nanos(values: &[u64]) -> Vec<Duration> {
+        values.iter().map(|x| Duration::from_nanos(*x)).collect()
+    }
+
+    #[test]
+    fn summarizes_samples() {
+        let stats = BenchStats::from_samples(&nanos(&[40, 10, 30, 20, 50])).unwrap();
+        assert_eq!(stats.samples, 5);
+        assert_eq!(stats.min, Duration::from_nanos(10));
+        assert_eq!(stats.median, Duration::from_nanos(30));
+        assert_eq!(stats.mean, Duration::from_nanos(30));
+        assert_eq!(stats.p95, Duration::from_nanos(50));
+        assert_eq!(stats.max, Duration::from_nanos(50));
+        // population standard deviation of 10, 20, 30, 40, 50 is sqrt(200)
+        assert_eq!(stats.std_dev, Duration::from_nanos(14));
+        assert_eq!(stats.outliers, 0);
+    }
+
+    #[test]
+    fn takes_mean_of_middle_samples_for_median() {
+        let stats = BenchStats::from_samples(&nanos(&[10, 20, 30, 41])).unwrap();
+        assert_eq!(stats.median, Duration::from_nanos(25));
+    }
+
+    #[test]
+    fn detects_outliers() {
+        let mut samples = vec![100; 18];
+        samples.extend([101, 10_000]);
+        let stats = BenchStats::from_samples(&nanos(&samples)).unwrap();
+        assert_eq!(stats.outliers, 2);
+        assert_eq!(stats.median, Duration::from_nanos(100));
+        assert_eq!(stats.max, Duration::from_nanos(10_000));
+        assert!(stats.mean > stats.median);
+    }
+
+    #[test]
+    fn handles_no_samples() {
+        assert_eq!(BenchStats::from_samples(&[]), None);
+    }
+
+    #[test]
+    fn round_trips_json() {
+        let stats = BenchStats::from_samples(&nanos(&[5, 7, 9, 1_000])).unwrap();
+        let json = JsonValue::from(&stats);
+        assert_eq!(BenchStats::try_from(&json), Ok(stats));
+    }
+}
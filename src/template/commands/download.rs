@@ -1,14 +1,16 @@
-use crate::template::{aoc_cli, Day};
+use crate::template::{aoc_client, Puzzle};
 use std::process;
 
-pub fn handle(day: Day) {
-    if aoc_cli::check().is_err() {
-        eprintln!("command \"aoc\" not found or not callable. Try running \"cargo install aoc-cli\" to install it.");
+pub fn handle(puzzle: Puzzle) {
+    let client = aoc_client::client();
+
+    if let Err(e) = client.check() {
+        eprintln!("{e}");
         process::exit(1);
     }
 
-    if let Err(e) = aoc_cli::download(day) {
-        eprintln!("failed to call aoc-cli: {e}");
+    if let Err(e) = client.download(puzzle) {
+        eprintln!("failed to call {}: {e}", client.name());
         process::exit(1);
     };
 }
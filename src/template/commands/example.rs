@@ -0,0 +1,58 @@
+use std::process;
+
+use crate::template::examples::Examples;
+use crate::template::{registry, Puzzle, ANSI_BOLD, ANSI_RESET};
+
+pub fn handle(puzzle: Puzzle) {
+    let Some(solution) = registry::find(puzzle) else {
+        eprintln!("There is no solution for {puzzle} yet.");
+        process::exit(1);
+    };
+
+    let examples = match Examples::read(puzzle) {
+        Ok(Some(examples)) => examples,
+        Ok(None) => {
+            eprintln!(
+                "No examples manifest at \"{}\".",
+                Examples::manifest_path(puzzle).display()
+            );
+            process::exit(1);
+        }
+        Err(e) => {
+            eprintln!("{e}");
+            process::exit(1);
+        }
+    };
+
+    let examples_dir = puzzle.year.data_dir("data").join("examples");
+    let results = examples.run(solution, &examples_dir).unwrap_or_else(|e| {
+        eprintln!("{e}");
+        process::exit(1);
+    });
+
+    let mut failures = 0;
+    let mut current_file = None;
+
+    for result in &results {
+        if current_file != Some(&result.file) {
+            if current_file.is_some() {
+                println!();
+            }
+            println!("{ANSI_BOLD}{}{ANSI_RESET}", result.file);
+            println!("------");
+            current_file = Some(&result.file);
+        }
+
+        println!("{result}");
+        if !result.is_pass() {
+            failures += 1;
+        }
+    }
+
+    if failures > 0 {
+        eprintln!("\n{failures} example case(s) failed.");
+        process::exit(1);
+    }
+
+    println!("\nAll {} example case(s) passed.", results.len());
+}
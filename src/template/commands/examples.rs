@@ -0,0 +1,151 @@
+use std::{fs, path::Path, process};
+
+use crate::template::examples::Examples;
+use crate::template::puzzle_examples::PuzzleExamples;
+use crate::template::{Puzzle, ANSI_BOLD, ANSI_RESET};
+
+/// Write an example file, keeping an existing one that is not empty unless `overwrite` is set.
+/// Returns whether the file now holds `content`.
+fn write_example(path: &Path, content: &str, overwrite: bool) -> bool {
+    let existing = fs::read_to_string(path).unwrap_or_default();
+
+    if existing == content {
+        println!("Example file \"{}\" is up to date.", path.display());
+        return true;
+    }
+
+    if !existing.trim().is_empty() && !overwrite {
+        println!(
+            "Kept existing example file \"{}\", use `--overwrite` to replace it.",
+            path.display()
+        );
+        return false;
+    }
+
+    if let Err(e) =
+        fs::create_dir_all(path.parent().unwrap()).and_then(|()| fs::write(path, content))
+    {
+        eprintln!("Failed to write example file: {e}");
+        process::exit(1);
+    }
+    println!("Wrote example file \"{}\".", path.display());
+    true
+}
+
+/// `block` picks the main example by its number in the list, instead of guessing it.
+pub fn handle(puzzle: Puzzle, block: Option<usize>, overwrite: bool) {
+    let description_path = puzzle.data_file("data", "puzzles", "md");
+    let Ok(description) = fs::read_to_string(&description_path) else {
+        eprintln!(
+            "Could not read \"{}\", download the puzzle first.",
+            description_path.display()
+        );
+        process::exit(1);
+    };
+
+    let found = PuzzleExamples::parse(&description);
+
+    if found.blocks.is_empty() {
+        eprintln!(
+            "Found no code blocks in \"{}\".",
+            description_path.display()
+        );
+        process::exit(1);
+    }
+
+    let main_1 = match block {
+        Some(number) if (1..=found.blocks.len()).contains(&number) => Some(number - 1),
+        Some(number) => {
+            eprintln!(
+                "There is no block {number}, expected 1 to {}.",
+                found.blocks.len()
+            );
+            process::exit(1);
+        }
+        None => found.main_block(1),
+    };
+    // part two gets an example of its own only if it differs from the main one.
+    let main_2 = found.main_block(2).filter(|_| block.is_none()).filter(|i| {
+        Some(found.blocks[*i].content.as_str()) != main_1.map(|m| found.blocks[m].content.as_str())
+    });
+
+    println!(
+        "{ANSI_BOLD}Code blocks in \"{}\"{ANSI_RESET}",
+        description_path.display()
+    );
+    for (i, candidate) in found.blocks.iter().enumerate() {
+        let lines = candidate.content.lines().count();
+        let role = if Some(i) == main_1 {
+            "  <- example"
+        } else if Some(i) == main_2 {
+            "  <- example for part 2"
+        } else {
+            ""
+        };
+        println!(
+            "{:>3}. part {}, {lines} line(s): {}{role}",
+            i + 1,
+            candidate.part,
+            candidate.preview()
+        );
+    }
+    println!();
+
+    let examples_dir = puzzle.year.data_dir("data").join("examples");
+    let mut manifest = match Examples::read(puzzle) {
+        Ok(manifest) => manifest.unwrap_or_default(),
+        Err(e) => {
+            eprintln!("{e}");
+            process::exit(1);
+        }
+    };
+
+    let mut changed = false;
+    let mut add_case = |file: String, index: usize, parts: &[u8]| {
+        if !write_example(
+            &examples_dir.join(&file),
+            &found.blocks[index].content,
+            overwrite,
+        ) {
+            return;
+        }
+
+        let case = manifest.case_mut(&file);
+        for &part in parts {
+            match found.answer(part) {
+                Some(answer) if overwrite || case.expected(part).is_none() => {
+                    println!("Part {part}: expecting {answer} for \"{file}\".");
+                    case.set_expected(part, answer);
+                }
+                Some(_) => println!("Part {part}: kept the expected answer for \"{file}\"."),
+                None => println!("Part {part}: found no answer, fill it in by hand."),
+            }
+        }
+        changed = true;
+    };
+
+    match (main_1, main_2) {
+        (Some(main_1), Some(main_2)) => {
+            add_case(format!("{}.txt", puzzle.day), main_1, &[1]);
+            add_case(format!("{}-2.txt", puzzle.day), main_2, &[2]);
+        }
+        (Some(main), None) | (None, Some(main)) => {
+            add_case(format!("{}.txt", puzzle.day), main, &[1, 2]);
+        }
+        (None, None) => {}
+    }
+
+    if changed {
+        match manifest.store(puzzle) {
+            Ok(()) => println!(
+                "Updated \"{}\", check it with `cargo example {}`.",
+                Examples::manifest_path(puzzle).display(),
+                puzzle.day
+            ),
+            Err(e) => {
+                eprintln!("Failed to store examples manifest: {e}");
+                process::exit(1);
+            }
+        }
+    }
+}
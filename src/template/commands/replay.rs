@@ -0,0 +1,189 @@
+use std::fs;
+use std::io::{stdin, stdout, IsTerminal, Read, Write};
+use std::process::{self, Command, Stdio};
+use std::sync::mpsc::{self, Receiver, RecvTimeoutError};
+use std::thread;
+use std::time::Duration;
+
+use crate::template::replay::{parse_replay, replay_path, Control, Player, ReplayFrame};
+use crate::template::{Puzzle, ANSI_BOLD, ANSI_ITALIC, ANSI_RESET};
+
+const ANSI_HOME: &str = "\x1b[H";
+const ANSI_CLEAR: &str = "\x1b[2J";
+const ANSI_CLEAR_LINE: &str = "\x1b[K";
+const ANSI_CLEAR_BELOW: &str = "\x1b[J";
+const ANSI_HIDE_CURSOR: &str = "\x1b[?25l";
+const ANSI_SHOW_CURSOR: &str = "\x1b[?25h";
+
+pub const DEFAULT_FPS: u32 = 10;
+
+pub fn handle(puzzle: Puzzle, part: Option<u8>, fps: u32) {
+    let path = replay_path(puzzle);
+    let Ok(text) = fs::read_to_string(&path) else {
+        eprintln!(
+            "No replay at \"{}\", record one with `cargo solve {} --record`.",
+            path.display(),
+            puzzle.day
+        );
+        process::exit(1);
+    };
+
+    let frames: Vec<ReplayFrame> = match parse_replay(&text) {
+        Ok(frames) => frames
+            .into_iter()
+            .filter(|frame| part.is_none_or(|part| frame.part == part))
+            .collect(),
+        Err(e) => {
+            eprintln!("Could not read \"{}\": {e}", path.display());
+            process::exit(1);
+        }
+    };
+
+    if frames.is_empty() {
+        eprintln!("The replay has no frames, does the solution emit any?");
+        process::exit(1);
+    }
+
+    // without a terminal to play back in, e.g. when piped, print the frames one after another.
+    if !stdout().is_terminal() {
+        for frame in &frames {
+            println!("{frame}");
+        }
+        return;
+    }
+
+    play(&frames, fps);
+}
+
+/// Play the frames back in place, reading controls from stdin until `q` is pressed.
+fn play(frames: &[ReplayFrame], fps: u32) {
+    let raw_mode = RawMode::enable();
+    let keys = read_keys();
+    let mut player = Player::new(frames.len(), fps);
+
+    print!("{ANSI_CLEAR}{ANSI_HIDE_CURSOR}");
+
+    loop {
+        draw(
+            &frames[player.index],
+            &player,
+            frames.len(),
+            raw_mode.is_some(),
+        );
+
+        let key = if player.playing {
+            match keys.recv_timeout(Duration::from_secs(1) / player.fps) {
+                Ok(key) => Some(key),
+                Err(RecvTimeoutError::Timeout) => None,
+                Err(RecvTimeoutError::Disconnected) => break,
+            }
+        } else {
+            match keys.recv() {
+                Ok(key) => Some(key),
+                Err(_) => break,
+            }
+        };
+
+        match key.map(|key| Control::from_key(&key)) {
+            None => player.tick(),
+            Some(Some(control)) => {
+                if !player.control(control) {
+                    break;
+                }
+            }
+            // other keys are ignored.
+            Some(None) => {}
+        }
+    }
+
+    print!("{ANSI_SHOW_CURSOR}");
+    let _ = stdout().flush();
+}
+
+fn draw(frame: &ReplayFrame, player: &Player, frames: usize, raw_mode: bool) {
+    let mut out = String::from(ANSI_HOME);
+
+    out.push_str(&format!(
+        "{ANSI_BOLD}Part {}, frame {}: {}{ANSI_RESET}{ANSI_CLEAR_LINE}\n",
+        frame.part, frame.number, frame.title
+    ));
+    for line in frame.picture.lines() {
+        out.push_str(&format!("{line}{ANSI_CLEAR_LINE}\n"));
+    }
+
+    let state = if player.playing { "playing" } else { "paused" };
+    let enter = if raw_mode { "" } else { ", followed by Enter" };
+    out.push_str(&format!(
+        "\n{}/{frames} · {state} · {} fps{ANSI_CLEAR_LINE}\n",
+        player.index + 1,
+        player.fps
+    ));
+    out.push_str(&format!(
+        "{ANSI_ITALIC}space play/pause · ←/→ or p/n step · g/G first/last · +/- speed · q quit{enter}{ANSI_RESET}{ANSI_CLEAR_LINE}\n{ANSI_CLEAR_BELOW}"
+    ));
+
+    print!("{out}");
+    let _ = stdout().flush();
+}
+
+/// Read key presses from stdin on a thread, keeping escape sequences such as arrow keys together.
+fn read_keys() -> Receiver<Vec<u8>> {
+    let (sender, receiver) = mpsc::channel();
+
+    thread::spawn(move || {
+        let mut bytes = stdin().lock().bytes().map_while(Result::ok);
+
+        while let Some(byte) = bytes.next() {
+            let key = match byte {
+                // `ESC [ X`, the arrow keys and home/end.
+                b'\x1b' => [byte].into_iter().chain(bytes.by_ref().take(2)).collect(),
+                // lines typed without raw mode end with Enter.
+                b'\n' | b'\r' => continue,
+                _ => vec![byte],
+            };
+
+            if sender.send(key).is_err() {
+                break;
+            }
+        }
+    });
+
+    receiver
+}
+
+/// Reads single key presses without echoing them while alive, using `stty`.
+/// Where `stty` is not available, keys are read line by line instead.
+struct RawMode {
+    saved: String,
+}
+
+impl RawMode {
+    fn enable() -> Option<Self> {
+        let output = Command::new("stty")
+            .arg("-g")
+            .stdin(Stdio::inherit())
+            .output()
+            .ok()
+            .filter(|output| output.status.success())?;
+        let saved = String::from_utf8(output.stdout).ok()?.trim().to_string();
+
+        // `-isig` turns Ctrl-C into a key, so the terminal is always restored.
+        Command::new("stty")
+            .args(["-icanon", "-echo", "-isig", "min", "1"])
+            .stdin(Stdio::inherit())
+            .status()
+            .ok()
+            .filter(|status| status.success())?;
+
+        Some(Self { saved })
+    }
+}
+
+impl Drop for RawMode {
+    fn drop(&mut self) {
+        let _ = Command::new("stty")
+            .arg(&self.saved)
+            .stdin(Stdio::inherit())
+            .status();
+    }
+}
@@ -1,15 +1,26 @@
 use std::{
-    fs::{File, OpenOptions},
-    io::Write,
+    fs::{self, File, OpenOptions},
+    io::{ErrorKind, Write},
+    path::{Path, PathBuf},
     process,
 };
 
-use crate::template::Day;
+use crate::template::Puzzle;
 
-const MODULE_TEMPLATE: &str =
-    include_str!(concat!(env!("CARGO_MANIFEST_DIR"), "/src/template.txt"));
+/// Used when `templates/default.txt` has been removed.
+const DEFAULT_TEMPLATE: &str = include_str!(concat!(
+    env!("CARGO_MANIFEST_DIR"),
+    "/templates/default.txt"
+));
 
-fn safe_create_file(path: &str, overwrite: bool) -> Result<File, std::io::Error> {
+pub const DEFAULT_TEMPLATE_NAME: &str = "default";
+
+const TEMPLATES_DIR: &str = "templates";
+
+fn safe_create_file(path: &Path, overwrite: bool) -> Result<File, std::io::Error> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
     let mut file = OpenOptions::new();
     if overwrite {
         file.create(true);
@@ -19,18 +30,110 @@ fn safe_create_file(path: &str, overwrite: bool) -> Result<File, std::io::Error>
     file.truncate(true).write(true).open(path)
 }
 
-fn create_file(path: &str) -> Result<File, std::io::Error> {
-    OpenOptions::new()
-        .write(true)
-        .create(true)
-        .truncate(true)
-        .open(path)
+/// The arguments to `solution!`: just the day for the home year, `year: YYYY, N` for any other year.
+fn solution_args(puzzle: Puzzle) -> String {
+    let day = puzzle.day.into_inner();
+    if puzzle.year.is_home() {
+        day.to_string()
+    } else {
+        format!("year: {}, {day}", puzzle.year)
+    }
+}
+
+/// The puzzle's title, taken from its description if it has been downloaded already.
+fn title(puzzle: Puzzle) -> String {
+    fs::read_to_string(puzzle.data_file("data", "puzzles", "md"))
+        .ok()
+        .and_then(|description| parse_title(&description))
+        .unwrap_or_else(|| format!("Day {}", puzzle.day.into_inner()))
 }
 
-pub fn handle(day: Day, overwrite: bool) {
-    let input_path = format!("data/inputs/{day}.txt");
-    let example_path = format!("data/examples/{day}.txt");
-    let module_path = format!("src/bin/{day}.rs");
+/// Parse the title out of a heading like `## --- Day 12: Garden Groups ---`,
+/// or `\--- Day 12: Garden Groups ---` as aoc-cli writes it.
+fn parse_title(description: &str) -> Option<String> {
+    let heading = description.lines().find_map(|line| {
+        line.strip_prefix("## --- Day ")
+            .or_else(|| line.strip_prefix("\\--- Day "))
+    })?;
+    let (_, title) = heading.split_once(": ")?;
+    Some(title.trim_end_matches(" ---").trim().into())
+}
+
+/// Fill in the placeholders of a template:
+///  - `%DAY_NUMBER%`: the arguments to `solution!`, see [`solution_args`].
+///  - `%YEAR%` and `%DAY%`: the year and the zero-padded day.
+///  - `%TITLE%`: the puzzle's title.
+///  - `%PART_ONE%` and `%PART_TWO%`: the names of the part functions that `solution!` runs.
+fn render(template: &str, puzzle: Puzzle, title: &str) -> String {
+    template
+        .replace("%DAY_NUMBER%", &solution_args(puzzle))
+        .replace("%YEAR%", &puzzle.year.to_string())
+        .replace("%DAY%", &puzzle.day.to_string())
+        .replace("%TITLE%", title)
+        .replace("%PART_ONE%", "part_one")
+        .replace("%PART_TWO%", "part_two")
+}
+
+/// The names of the templates in `templates/`.
+fn available_templates() -> Vec<String> {
+    let Ok(entries) = fs::read_dir(TEMPLATES_DIR) else {
+        return vec![DEFAULT_TEMPLATE_NAME.into()];
+    };
+
+    let mut names: Vec<String> = entries
+        .filter_map(Result::ok)
+        .map(|entry| entry.path())
+        .filter(|path| path.extension().is_some_and(|ext| ext == "txt"))
+        .filter_map(|path| Some(path.file_stem()?.to_str()?.to_string()))
+        .collect();
+
+    names.sort_unstable();
+    names
+}
+
+fn read_template(name: &str) -> Result<String, String> {
+    let path: PathBuf = Path::new(TEMPLATES_DIR).join(format!("{name}.txt"));
+
+    match fs::read_to_string(&path) {
+        Ok(template) => Ok(template),
+        Err(_) if name == DEFAULT_TEMPLATE_NAME => Ok(DEFAULT_TEMPLATE.into()),
+        Err(_) => Err(format!(
+            "Unknown template \"{name}\", expected one of: {}.",
+            available_templates().join(", ")
+        )),
+    }
+}
+
+/// Create an empty data file. An existing one is only emptied with `overwrite`.
+fn create_data_file(path: &Path, kind: &str, overwrite: bool) {
+    match safe_create_file(path, overwrite) {
+        Ok(_) => {
+            println!("Created empty {kind} file \"{}\"", path.display());
+        }
+        Err(e) if e.kind() == ErrorKind::AlreadyExists => {
+            println!("Kept existing {kind} file \"{}\"", path.display());
+        }
+        Err(e) => {
+            eprintln!("Failed to create {kind} file: {e}");
+            process::exit(1);
+        }
+    }
+}
+
+/// Create the solution and its empty data files. Existing files are kept unless `overwrite` is set,
+/// but an input that was just `downloaded` is never emptied.
+pub fn handle(puzzle: Puzzle, template: &str, overwrite: bool, downloaded: bool) {
+    let input_path = puzzle.data_file("data", "inputs", "txt");
+    let example_path = puzzle.data_file("data", "examples", "txt");
+    let module_path = Path::new("src/bin").join(format!("{}.rs", puzzle.bin_name()));
+
+    let template = match read_template(template) {
+        Ok(template) => template,
+        Err(e) => {
+            eprintln!("{e}");
+            process::exit(1);
+        }
+    };
 
     let mut file = match safe_create_file(&module_path, overwrite) {
         Ok(file) => file,
@@ -40,13 +143,9 @@ pub fn handle(day: Day, overwrite: bool) {
         }
     };
 
-    match file.write_all(
-        MODULE_TEMPLATE
-            .replace("%DAY_NUMBER%", &day.into_inner().to_string())
-            .as_bytes(),
-    ) {
+    match file.write_all(render(&template, puzzle, &title(puzzle)).as_bytes()) {
         Ok(()) => {
-            println!("Created module file \"{}\"", &module_path);
+            println!("Created module file \"{}\"", module_path.display());
         }
         Err(e) => {
             eprintln!("Failed to write module contents: {e}");
@@ -54,26 +153,59 @@ pub fn handle(day: Day, overwrite: bool) {
         }
     }
 
-    match create_file(&input_path) {
-        Ok(_) => {
-            println!("Created empty input file \"{}\"", &input_path);
-        }
-        Err(e) => {
-            eprintln!("Failed to create input file: {e}");
-            process::exit(1);
-        }
+    create_data_file(&input_path, "input", overwrite && !downloaded);
+    create_data_file(&example_path, "example", overwrite);
+
+    println!("---");
+    if puzzle.year.is_home() {
+        println!("🎄 Type `cargo solve {}` to run your solution.", puzzle.day);
+    } else {
+        println!(
+            "🎄 Type `cargo solve {} --year {}` to run your solution.",
+            puzzle.day, puzzle.year
+        );
     }
+}
 
-    match create_file(&example_path) {
-        Ok(_) => {
-            println!("Created empty example file \"{}\"", &example_path);
-        }
-        Err(e) => {
-            eprintln!("Failed to create example file: {e}");
-            process::exit(1);
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{parse_title, render, DEFAULT_TEMPLATE};
+    use crate::{day, template::Puzzle, year};
+
+    #[test]
+    fn parses_titles() {
+        assert_eq!(
+            parse_title("## --- Day 12: Garden Groups ---\n\nSome text."),
+            Some("Garden Groups".into())
+        );
+        assert_eq!(
+            parse_title("\\--- Day 12: Garden Groups ---\n----------\n\nSome text."),
+            Some("Garden Groups".into())
+        );
+        assert_eq!(parse_title("# Day 12\n"), None);
+    }
+
+    #[test]
+    fn renders_placeholders() {
+        let template = "%YEAR%/%DAY%: %TITLE%\nsolution!(%DAY_NUMBER%);\nfn %PART_ONE%() {}\nfn %PART_TWO%() {}";
+        assert_eq!(
+            render(template, Puzzle::from(day!(7)), "Bridge Repair"),
+            format!(
+                "{}/07: Bridge Repair\nsolution!(7);\nfn part_one() {{}}\nfn part_two() {{}}",
+                crate::template::Year::home()
+            )
+        );
+
+        let other = Puzzle::new(year!(2015), day!(7));
+        if !other.year.is_home() {
+            assert!(render(template, other, "").contains("solution!(year: 2015, 7);"));
         }
     }
 
-    println!("---");
-    println!("🎄 Type `cargo solve {day}` to run your solution.");
+    #[test]
+    fn default_template_has_no_placeholders_left() {
+        let rendered = render(DEFAULT_TEMPLATE, day!(1).into(), "Title");
+        assert!(!rendered.contains('%'));
+        assert!(rendered.contains("pub fn part_one"));
+    }
 }
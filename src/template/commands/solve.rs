@@ -1,9 +1,70 @@
-use std::process::{Command, Stdio};
+use std::process::{self, Command, Stdio};
 
-use crate::template::Day;
+use crate::template::inputs::Input;
+use crate::template::params::Param;
+use crate::template::replay::replay_path;
+use crate::template::watch::{watch, watched_paths};
+use crate::template::{Puzzle, ANSI_BOLD, ANSI_ITALIC, ANSI_RESET};
 
-pub fn handle(day: Day, release: bool, dhat: bool, submit_part: Option<u8>) {
-    let mut cmd_args = vec!["run".to_string(), "--bin".to_string(), day.to_string()];
+const ANSI_CLEAR: &str = "\x1b[2J\x1b[H";
+
+/// The arguments that are passed on to the solution binary.
+#[derive(Clone, Debug, Default)]
+pub struct SolutionArgs {
+    pub submit_part: Option<u8>,
+    pub format: Option<String>,
+    pub input: Option<String>,
+    pub params: Vec<Param>,
+    pub visualize: bool,
+    pub frames: Option<String>,
+    /// Record the frames for `cargo replay`.
+    pub record: bool,
+}
+
+impl SolutionArgs {
+    fn to_args(&self, puzzle: Puzzle) -> Vec<String> {
+        let mut args = vec![];
+
+        if let Some(submit_part) = self.submit_part {
+            args.push("--submit".to_string());
+            args.push(submit_part.to_string());
+        }
+
+        if let Some(format) = &self.format {
+            args.push("--format".to_string());
+            args.push(format.clone());
+        }
+
+        if let Some(input) = &self.input {
+            args.push("--input".to_string());
+            args.push(input.clone());
+        }
+
+        for param in &self.params {
+            args.push("--param".to_string());
+            args.push(param.to_string());
+        }
+
+        if self.visualize {
+            args.push("--visualize".to_string());
+        }
+
+        if let Some(frames) = &self.frames {
+            args.push("--frames".to_string());
+            args.push(frames.clone());
+        }
+
+        if self.record {
+            args.push("--record".to_string());
+            args.push(replay_path(puzzle).to_string_lossy().into_owned());
+        }
+
+        args
+    }
+}
+
+pub fn handle(puzzle: Puzzle, release: bool, dhat: bool, args: &SolutionArgs, watch_files: bool) {
+    let mut cmd_args = vec!["run".to_string(), "--bin".to_string(), puzzle.bin_name()];
 
     if dhat {
         cmd_args.extend([
@@ -17,18 +78,43 @@ pub fn handle(day: Day, release: bool, dhat: bool, submit_part: Option<u8>) {
     }
 
     cmd_args.push("--".to_string());
+    cmd_args.extend(args.to_args(puzzle));
 
-    if let Some(submit_part) = submit_part {
-        cmd_args.push("--submit".to_string());
-        cmd_args.push(submit_part.to_string());
+    if !watch_files {
+        // pass on failures, e.g. a part that returned an error.
+        if !run_cargo(&cmd_args) {
+            process::exit(1);
+        }
+        return;
     }
 
+    if args.submit_part.is_some() {
+        eprintln!("`--submit` cannot be combined with `--watch`.");
+        process::exit(1);
+    }
+
+    // the solution prints its examples next to their expected answers before the real input.
+    cmd_args.push("--examples".to_string());
+
+    let input = args.input.as_deref().map_or(Input::Own, Input::parse);
+    watch(
+        || watched_paths(puzzle, &input),
+        || {
+            print!("{ANSI_CLEAR}");
+            println!("{ANSI_BOLD}Watching {puzzle}{ANSI_RESET} {ANSI_ITALIC}(press Ctrl-C to stop){ANSI_RESET}\n");
+            run_cargo(&cmd_args);
+        },
+    );
+}
+
+/// Returns whether the command succeeded.
+fn run_cargo(cmd_args: &[String]) -> bool {
     let mut cmd = Command::new("cargo")
-        .args(&cmd_args)
+        .args(cmd_args)
         .stdout(Stdio::inherit())
         .stderr(Stdio::inherit())
         .spawn()
         .unwrap();
 
-    cmd.wait().unwrap();
+    cmd.wait().unwrap().success()
 }
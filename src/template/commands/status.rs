@@ -0,0 +1,29 @@
+use std::process;
+
+use tinyjson::JsonValue;
+
+use crate::template::status::Status;
+use crate::template::{Year, ANSI_BOLD, ANSI_RESET};
+
+pub fn handle(year: Year, format: Option<&str>, verify: bool) {
+    let status = match Status::collect(year, verify) {
+        Ok(status) => status,
+        Err(e) => {
+            eprintln!("{e}");
+            process::exit(1);
+        }
+    };
+
+    match format {
+        None | Some("text") => {
+            println!("{ANSI_BOLD}Advent of Code {year}{ANSI_RESET}\n");
+            println!("{status}");
+        }
+        // stringify never fails for values built from `From<&Status>`.
+        Some("json") => println!("{}", JsonValue::from(&status).stringify().unwrap()),
+        Some(format) => {
+            eprintln!("Unknown format \"{format}\", expected \"text\" or \"json\".");
+            process::exit(1);
+        }
+    }
+}
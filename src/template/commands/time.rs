@@ -1,16 +1,40 @@
 use std::collections::HashSet;
+use std::process;
 
+use crate::template::bench::BenchConfig;
+use crate::template::compare;
 use crate::template::run_multi::run_multi;
 use crate::template::timings::Timings;
-use crate::template::{all_days, readme_benchmarks, Day};
+use crate::template::{all_days, readme_benchmarks, Day, Year};
 
-pub fn handle(day: Option<Day>, run_all: bool, store: bool) {
-    let stored_timings = Timings::read_from_file();
+/// Regression threshold for `--compare`, in percent, when none is passed.
+pub const DEFAULT_COMPARE_THRESHOLD: f64 = 10.0;
+
+pub fn handle(
+    year: Year,
+    day: Option<Day>,
+    run_all: bool,
+    store: bool,
+    bench: BenchConfig,
+    compare_threshold: Option<f64>,
+    jobs: usize,
+) {
+    if store && jobs != 1 {
+        eprintln!(
+            "Timings benched in parallel are not stored, run without `--jobs` to use `--store`."
+        );
+        process::exit(1);
+    }
+
+    let stored_timings = Timings::read_from_file(year);
 
     let days_to_run = day.map_or_else(
         || {
             if run_all {
                 all_days().collect()
+            } else if compare_threshold.is_some() {
+                // when comparing, rerun every day that has something to compare against.
+                stored_timings.data.iter().map(|t| t.day).collect()
             } else {
                 // when the `--all` flag is not set, filter out days that are fully benched.
                 all_days()
@@ -21,14 +45,21 @@ pub fn handle(day: Option<Day>, run_all: bool, store: bool) {
         |day| HashSet::from([day]),
     );
 
-    let timings = run_multi(&days_to_run, true, true).unwrap();
+    let timings = run_multi(year, &days_to_run, Some(bench), jobs).unwrap();
+
+    let has_regressions = compare_threshold.is_some_and(|threshold| {
+        let deltas = compare::compare(&stored_timings, &timings);
+        println!();
+        compare::print_table(&deltas, threshold);
+        deltas.iter().any(|delta| delta.is_regression(threshold))
+    });
 
     if store {
         let merged_timings = stored_timings.merge(&timings);
-        merged_timings.store_file().unwrap();
+        merged_timings.store_file(year).unwrap();
 
         println!();
-        match readme_benchmarks::update(merged_timings) {
+        match readme_benchmarks::update(merged_timings, year) {
             Ok(()) => {
                 println!("Stored updated benchmarks.");
             }
@@ -37,4 +68,9 @@ pub fn handle(day: Option<Day>, run_all: bool, store: bool) {
             }
         }
     }
+
+    if has_regressions {
+        eprintln!("Some parts regressed beyond the threshold.");
+        process::exit(1);
+    }
 }
@@ -0,0 +1,86 @@
+use std::collections::HashSet;
+use std::process;
+
+use crate::template::answers::{Answers, Verdict};
+use crate::template::inputs::Input;
+use crate::template::run_multi::run_day;
+use crate::template::{all_days, Day, Puzzle, Year, ANSI_BOLD, ANSI_RESET};
+
+pub fn handle(year: Year, day: Option<Day>, run_all: bool, record: bool) {
+    let mut answers = match Answers::read_from_file(year) {
+        Ok(answers) => answers,
+        Err(e) => {
+            eprintln!("{e}");
+            process::exit(1);
+        }
+    };
+
+    let days_to_run: HashSet<Day> = day.map_or_else(
+        || {
+            if run_all {
+                all_days().collect()
+            } else {
+                // when the `--all` flag is not set, only verify days that have recorded answers.
+                answers.data.iter().map(|a| a.day).collect()
+            }
+        },
+        |day| HashSet::from([day]),
+    );
+
+    let mut failures = 0;
+    let mut recorded = 0;
+
+    for day in all_days().filter(|day| days_to_run.contains(day)) {
+        let puzzle = Puzzle::new(year, day);
+
+        // your own input first, then every named input, each against its own answers.
+        for input in [Input::Own].into_iter().chain(Input::list_named(puzzle)) {
+            match input.name() {
+                Some(name) => println!("{ANSI_BOLD}Day {day} ({name}){ANSI_RESET}"),
+                None => println!("{ANSI_BOLD}Day {day}{ANSI_RESET}"),
+            }
+            println!("------");
+
+            let reports = run_day(puzzle, &input, None, true).unwrap_or_else(|e| {
+                eprintln!("{e}");
+                vec![]
+            });
+
+            if reports.is_empty() {
+                println!("Not solved.");
+            }
+
+            for report in reports {
+                let verdict =
+                    answers.check(day, input.name(), report.part, report.answer.as_deref());
+
+                match (&verdict, &report.answer) {
+                    (Verdict::Missing, Some(answer)) if record => {
+                        answers.set(day, input.name(), report.part, answer);
+                        recorded += 1;
+                        println!("Part {}: {verdict} -> recorded", report.part);
+                    }
+                    _ => println!("Part {}: {verdict}", report.part),
+                }
+
+                if matches!(verdict, Verdict::Fail { .. }) {
+                    failures += 1;
+                }
+            }
+
+            println!();
+        }
+    }
+
+    if recorded > 0 {
+        match answers.store_file(year) {
+            Ok(()) => println!("Recorded {recorded} new answer(s)."),
+            Err(_) => eprintln!("Failed to store answers."),
+        }
+    }
+
+    if failures > 0 {
+        eprintln!("{failures} part(s) failed verification.");
+        process::exit(1);
+    }
+}
@@ -0,0 +1,154 @@
+/// Module that compares fresh benchmark timings against the ones stored in `timings.json`.
+use std::time::Duration;
+
+use crate::template::timings::Timings;
+use crate::template::{Day, ANSI_BOLD, ANSI_ITALIC, ANSI_RESET};
+
+/// The change in duration of a single part between the stored and the current timings.
+#[derive(Clone, Debug, PartialEq)]
+pub struct PartDelta {
+    pub day: Day,
+    pub part: u8,
+    /// `None` if the part has no stored timing yet.
+    pub stored: Option<Duration>,
+    pub current: Duration,
+}
+
+impl PartDelta {
+    /// The relative change from the stored to the current duration, in percent.
+    pub fn change_percent(&self) -> Option<f64> {
+        let stored = self.stored?.as_secs_f64();
+        if stored == 0_f64 {
+            return None;
+        }
+        Some((self.current.as_secs_f64() - stored) / stored * 100_f64)
+    }
+
+    /// A part regresses if it got slower by more than `threshold_percent`.
+    pub fn is_regression(&self, threshold_percent: f64) -> bool {
+        self.change_percent()
+            .is_some_and(|change| change > threshold_percent)
+    }
+}
+
+/// Pair up every benched part in `current` with its stored timing.
+pub fn compare(stored: &Timings, current: &Timings) -> Vec<PartDelta> {
+    current
+        .data
+        .iter()
+        .flat_map(|timing| {
+            let stored_timing = stored.data.iter().find(|t| t.day == timing.day);
+            (1..=2).filter_map(move |part| {
+                Some(PartDelta {
+                    day: timing.day,
+                    part,
+                    stored: stored_timing.and_then(|t| t.part_duration(part)),
+                    current: timing.part_duration(part)?,
+                })
+            })
+        })
+        .collect()
+}
+
+/// Print the deltas as a table, highlighting regressions beyond `threshold_percent`.
+pub fn print_table(deltas: &[PartDelta], threshold_percent: f64) {
+    println!(
+        "{ANSI_BOLD}{:<6}{:<6}{:>12}{:>12}{:>10}{ANSI_RESET}",
+        "Day", "Part", "Stored", "Current", "Change"
+    );
+
+    for delta in deltas {
+        let stored = delta
+            .stored
+            .map_or_else(|| "-".into(), |x| format!("{x:.1?}"));
+        let current = format!("{:.1?}", delta.current);
+        let change = delta
+            .change_percent()
+            .map_or_else(|| "new".into(), |x| format!("{x:+.1}%"));
+
+        let line = format!(
+            "{:<6}{:<6}{stored:>12}{current:>12}{change:>10}",
+            delta.day.to_string(),
+            delta.part
+        );
+
+        if delta.is_regression(threshold_percent) {
+            println!("{ANSI_BOLD}{line}  regressed{ANSI_RESET}");
+        } else {
+            println!("{line}");
+        }
+    }
+
+    println!("{ANSI_ITALIC}Regression threshold: +{threshold_percent:.1}%{ANSI_RESET}");
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::time::Duration;
+
+    use super::{compare, PartDelta};
+    use crate::{
+        day,
+        template::timings::{Timing, Timings},
+    };
+
+    fn timing(day: u8, part_1: Option<&str>, part_2: Option<&str>) -> Timing {
+        Timing {
+            day: crate::template::Day::new(day).unwrap(),
+            part_1: part_1.map(Into::into),
+            part_2: part_2.map(Into::into),
+            total_nanos: 0_f64,
+            part_1_stats: None,
+            part_2_stats: None,
+        }
+    }
+
+    #[test]
+    fn pairs_parts_with_stored_timings() {
+        let stored = Timings {
+            data: vec![timing(1, Some("10.0ms"), Some("20.0ms"))],
+        };
+        let current = Timings {
+            data: vec![
+                timing(1, Some("11.0ms"), Some("15.0ms")),
+                timing(2, Some("1.0ms"), None),
+            ],
+        };
+
+        let deltas = compare(&stored, &current);
+        assert_eq!(deltas.len(), 3);
+        assert_eq!(
+            deltas[2],
+            PartDelta {
+                day: day!(2),
+                part: 1,
+                stored: None,
+                current: Duration::from_millis(1),
+            }
+        );
+
+        let changes: Vec<Option<i64>> = deltas
+            .iter()
+            .map(|d| d.change_percent().map(|x| x.round() as i64))
+            .collect();
+        assert_eq!(changes, vec![Some(10), Some(-25), None]);
+    }
+
+    #[test]
+    fn flags_regressions_beyond_threshold() {
+        let delta = PartDelta {
+            day: day!(1),
+            part: 1,
+            stored: Some(Duration::from_millis(10)),
+            current: Duration::from_millis(12),
+        };
+        assert!(delta.is_regression(10_f64));
+        assert!(!delta.is_regression(25_f64));
+
+        let new_part = PartDelta {
+            stored: None,
+            ..delta
+        };
+        assert!(!new_part.is_regression(0_f64));
+    }
+}
@@ -0,0 +1,530 @@
+//! Example test cases listed in a manifest per day, `data/examples/NN.json`:
+//!
+//! ```json
+//! { "cases": [
+//!     { "file": "12.txt", "part_1": 140, "part_2": "80" },
+//!     { "file": "12-2.txt", "part_2": 436 },
+//!     { "file": "11.txt", "params": { "blinks_part_one": 6 }, "part_1": 22 }
+//! ] }
+//! ```
+//!
+//! Files are relative to the examples folder. A part without an expected answer is not checked for that case.
+//! A case can override the solution's parameters with `params`, see [`crate::template::params`].
+//! Every solution gets a test that runs the cases of its manifest, see the `solution!` macro.
+use std::{
+    collections::HashMap,
+    fmt::Display,
+    fs,
+    io::Error,
+    path::{Path, PathBuf},
+    str::FromStr,
+};
+use tinyjson::JsonValue;
+
+use crate::template::{params::Param, registry::Solution, Puzzle};
+
+/// An example file and the answers expected for it.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ExampleCase {
+    pub file: String,
+    pub part_1: Option<String>,
+    pub part_2: Option<String>,
+    pub params: Vec<Param>,
+}
+
+impl ExampleCase {
+    /// The file, followed by the parameters it overrides, e.g. `11.txt (blinks_part_one=6)`.
+    pub fn name(&self) -> String {
+        if self.params.is_empty() {
+            return self.file.clone();
+        }
+
+        let params: Vec<String> = self.params.iter().map(Param::to_string).collect();
+        format!("{} ({})", self.file, params.join(", "))
+    }
+
+    pub fn expected(&self, part: u8) -> Option<&str> {
+        match part {
+            1 => self.part_1.as_deref(),
+            2 => self.part_2.as_deref(),
+            _ => None,
+        }
+    }
+
+    pub fn set_expected(&mut self, part: u8, answer: &str) {
+        match part {
+            1 => self.part_1 = Some(answer.into()),
+            2 => self.part_2 = Some(answer.into()),
+            _ => {}
+        }
+    }
+}
+
+/// The example cases of a day.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Examples {
+    pub cases: Vec<ExampleCase>,
+}
+
+/// The outcome of running one part on one example.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CaseResult {
+    /// The case's [`name`](ExampleCase::name).
+    pub file: String,
+    pub part: u8,
+    pub expected: String,
+    pub actual: Option<String>,
+}
+
+impl CaseResult {
+    pub fn is_pass(&self) -> bool {
+        self.actual.as_deref() == Some(self.expected.as_str())
+    }
+}
+
+impl Display for CaseResult {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match &self.actual {
+            _ if self.is_pass() => write!(f, "Part {}: PASS", self.part),
+            None => write!(
+                f,
+                "Part {}: FAIL (expected {}, got nothing)",
+                self.part, self.expected
+            ),
+            Some(actual) if !self.expected.contains('\n') && !actual.contains('\n') => write!(
+                f,
+                "Part {}: FAIL (expected {}, got {actual})",
+                self.part, self.expected
+            ),
+            Some(actual) => {
+                write!(f, "Part {}: FAIL", self.part)?;
+                for line in diff_lines(&self.expected, actual) {
+                    write!(f, "\n{line}")?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+/// A line by line diff of multi-line answers, e.g. grids: `-` marks expected lines, `+` actual ones.
+fn diff_lines(expected: &str, actual: &str) -> Vec<String> {
+    let expected: Vec<&str> = expected.lines().collect();
+    let actual: Vec<&str> = actual.lines().collect();
+
+    (0..expected.len().max(actual.len()))
+        .flat_map(|i| match (expected.get(i), actual.get(i)) {
+            (Some(e), Some(a)) if e == a => vec![format!("  {e}")],
+            (e, a) => e
+                .map(|e| format!("- {e}"))
+                .into_iter()
+                .chain(a.map(|a| format!("+ {a}")))
+                .collect(),
+        })
+        .collect()
+}
+
+impl Examples {
+    /// `data/examples/NN.json` for the home year, `data/<year>/examples/NN.json` for any other year.
+    pub fn manifest_path(puzzle: Puzzle) -> PathBuf {
+        puzzle.data_file("data", "examples", "json")
+    }
+
+    /// Dehydrate the manifest to a JSON file.
+    pub fn store(&self, puzzle: Puzzle) -> Result<(), Error> {
+        let path = Self::manifest_path(puzzle);
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let json = JsonValue::from(self.clone());
+        let mut file = fs::File::create(path)?;
+        json.format_to(&mut file)
+    }
+
+    /// The case for an example file, added without expected answers if it is not listed yet.
+    pub fn case_mut(&mut self, file: &str) -> &mut ExampleCase {
+        let index = match self.cases.iter().position(|case| case.file == file) {
+            Some(index) => index,
+            None => {
+                self.cases.push(ExampleCase {
+                    file: file.into(),
+                    part_1: None,
+                    part_2: None,
+                    params: vec![],
+                });
+                self.cases.len() - 1
+            }
+        };
+        &mut self.cases[index]
+    }
+
+    /// Read the manifest of a puzzle, `None` if it has none.
+    pub fn read(puzzle: Puzzle) -> Result<Option<Self>, String> {
+        let path = Self::manifest_path(puzzle);
+        if !path.exists() {
+            return Ok(None);
+        }
+
+        fs::read_to_string(&path)
+            .map_err(|e| e.to_string())
+            .and_then(Examples::try_from)
+            .map(Some)
+            .map_err(|e| format!("Could not read \"{}\": {e}", path.display()))
+    }
+
+    /// Run every listed part of every case, reading example files from `examples_dir`.
+    pub fn run(&self, solution: &Solution, examples_dir: &Path) -> Result<Vec<CaseResult>, String> {
+        let mut results = vec![];
+
+        for case in &self.cases {
+            let path = examples_dir.join(&case.file);
+            let input = fs::read_to_string(&path)
+                .map_err(|_| format!("Could not read example file \"{}\".", path.display()))?;
+
+            for part in solution.parts {
+                let Some(expected) = case.expected(part.part) else {
+                    continue;
+                };
+
+                results.push(CaseResult {
+                    file: case.name(),
+                    part: part.part,
+                    expected: expected.into(),
+                    actual: (part.run)(&input, &case.params, None, false).answer,
+                });
+            }
+        }
+
+        Ok(results)
+    }
+}
+
+/// Run the cases in a solution's manifest and panic with every failure.
+/// Solutions without a manifest pass.
+pub fn assert_examples(solution: &Solution) {
+    let examples = match Examples::read(solution.puzzle) {
+        Ok(Some(examples)) => examples,
+        Ok(None) => return,
+        Err(e) => panic!("{e}"),
+    };
+
+    let examples_dir = solution.puzzle.year.data_dir("data").join("examples");
+    let results = examples
+        .run(solution, &examples_dir)
+        .unwrap_or_else(|e| panic!("{e}"));
+
+    let failures: Vec<String> = results
+        .iter()
+        .filter(|result| !result.is_pass())
+        .map(|result| format!("{}: {result}", result.file))
+        .collect();
+
+    assert!(
+        failures.is_empty(),
+        "{} example case(s) failed:\n{}",
+        failures.len(),
+        failures.join("\n")
+    );
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<Examples> for JsonValue {
+    fn from(value: Examples) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert(
+            "cases".into(),
+            JsonValue::Array(value.cases.iter().map(JsonValue::from).collect()),
+        );
+
+        JsonValue::Object(map)
+    }
+}
+
+impl From<&ExampleCase> for JsonValue {
+    fn from(value: &ExampleCase) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert("file".into(), JsonValue::String(value.file.clone()));
+
+        for (key, answer) in [("part_1", &value.part_1), ("part_2", &value.part_2)] {
+            if let Some(answer) = answer {
+                map.insert(key.into(), JsonValue::String(answer.clone()));
+            }
+        }
+
+        if !value.params.is_empty() {
+            let params = value
+                .params
+                .iter()
+                .map(|param| (param.key.clone(), JsonValue::String(param.value.clone())))
+                .collect();
+            map.insert("params".into(), JsonValue::Object(params));
+        }
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<String> for Examples {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        let json = JsonValue::from_str(&value).or(Err("not valid JSON file."))?;
+
+        let json_cases = json
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("expected JSON document to be an object.")?
+            .get("cases")
+            .ok_or("expected JSON document to have key `cases`.")?
+            .get::<Vec<JsonValue>>()
+            .ok_or("expected `json.cases` to be an array.")?;
+
+        Ok(Examples {
+            cases: json_cases
+                .iter()
+                .map(ExampleCase::try_from)
+                .collect::<Result<_, _>>()?,
+        })
+    }
+}
+
+impl TryFrom<&JsonValue> for ExampleCase {
+    type Error = String;
+
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected case to be a JSON object.")?;
+
+        let file = json
+            .get("file")
+            .and_then(|v| v.get::<String>())
+            .ok_or("Expected case.file to be a string.")?;
+
+        // answers can be written as numbers for convenience.
+        let answer = |key: &str| match json.get(key) {
+            None | Some(JsonValue::Null) => Ok(None),
+            Some(JsonValue::String(s)) => Ok(Some(s.clone())),
+            Some(JsonValue::Number(n)) if n.fract() == 0.0 => Ok(Some(format!("{n:.0}"))),
+            Some(_) => Err(format!(
+                "Expected case.{key} to be null, a string or an integer."
+            )),
+        };
+
+        // parameter values can be written as numbers or booleans, too.
+        let mut params = match json.get("params") {
+            None | Some(JsonValue::Null) => vec![],
+            Some(JsonValue::Object(params)) => params
+                .iter()
+                .map(|(key, value)| {
+                    let value = match value {
+                        JsonValue::String(s) => s.clone(),
+                        JsonValue::Number(n) => n.to_string(),
+                        JsonValue::Boolean(b) => b.to_string(),
+                        _ => {
+                            return Err(format!(
+                                "Expected case.params.{key} to be a string, a number or a boolean."
+                            ))
+                        }
+                    };
+                    Ok(Param {
+                        key: key.clone(),
+                        value,
+                    })
+                })
+                .collect::<Result<_, _>>()?,
+            Some(_) => return Err("Expected case.params to be an object.".into()),
+        };
+        // JSON objects are unordered, sort them to keep the manifest stable.
+        params.sort_by(|a: &Param, b| a.key.cmp(&b.key));
+
+        Ok(ExampleCase {
+            file: file.clone(),
+            part_1: answer("part_1")?,
+            part_2: answer("part_2")?,
+            params,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::{env, fs, process, time::Duration};
+
+    use super::{CaseResult, ExampleCase, Examples};
+    use crate::{
+        day,
+        template::{
+            params::Param,
+            registry::{Solution, SolutionPart},
+            report::PartReport,
+            runner::solve_part_with_params,
+            Puzzle,
+        },
+    };
+
+    fn report(part: u8, answer: String) -> PartReport {
+        PartReport {
+            puzzle: day!(1).into(),
+            part,
+            answer: Some(answer),
+            error: None,
+            duration: Duration::ZERO,
+            samples: 1,
+            stats: None,
+        }
+    }
+
+    crate::params! {
+        struct Factor {
+            factor: usize = 1,
+        }
+    }
+
+    const PUZZLE: Puzzle = Puzzle::new(crate::template::Year::home(), day!(1));
+
+    /// Part 1 counts lines times the `factor` parameter, part 2 echoes the input.
+    const SOLUTION: Solution = Solution {
+        puzzle: PUZZLE,
+        parts: &[
+            SolutionPart {
+                part: 1,
+                run: |input, params, _, _| {
+                    solve_part_with_params(
+                        |input, params: &Factor| Some(input.lines().count() * params.factor),
+                        input,
+                        params,
+                        PUZZLE,
+                        1,
+                        None,
+                        false,
+                    )
+                },
+            },
+            SolutionPart {
+                part: 2,
+                run: |input, _, _, _| report(2, input.trim_end().into()),
+            },
+        ],
+    };
+
+    #[test]
+    fn parses_manifests() {
+        let json = r#"{ "cases": [
+            { "file": "01.txt", "part_1": 140, "part_2": "80" },
+            { "file": "01-2.txt", "part_2": null, "params": { "size": 7, "debug": true } }
+        ] }"#;
+        let examples = Examples::try_from(json.to_string()).unwrap();
+        assert_eq!(
+            examples.cases,
+            vec![
+                ExampleCase {
+                    file: "01.txt".into(),
+                    part_1: Some("140".into()),
+                    part_2: Some("80".into()),
+                    params: vec![],
+                },
+                ExampleCase {
+                    file: "01-2.txt".into(),
+                    part_1: None,
+                    part_2: None,
+                    params: vec!["debug=true".parse().unwrap(), "size=7".parse().unwrap()],
+                },
+            ]
+        );
+        assert!(Examples::try_from(r#"{ "cases": [{ "part_1": 1 }] }"#.to_string()).is_err());
+        assert!(
+            Examples::try_from(r#"{ "cases": [{ "file": "x", "part_1": 1.5 }] }"#.to_string())
+                .is_err()
+        );
+        assert!(
+            Examples::try_from(r#"{ "cases": [{ "file": "x", "params": [1] }] }"#.to_string())
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn runs_listed_cases() {
+        let dir = env::temp_dir().join(format!("aoc-examples-{}", process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(dir.join("a.txt"), "x\ny\n").unwrap();
+        fs::write(dir.join("b.txt"), "z\n").unwrap();
+
+        let examples = Examples {
+            cases: vec![
+                ExampleCase {
+                    file: "a.txt".into(),
+                    part_1: Some("2".into()),
+                    part_2: Some("x\nz".into()),
+                    params: vec![],
+                },
+                ExampleCase {
+                    file: "b.txt".into(),
+                    part_1: Some("1".into()),
+                    part_2: None,
+                    params: vec![],
+                },
+                ExampleCase {
+                    file: "b.txt".into(),
+                    part_1: Some("3".into()),
+                    part_2: None,
+                    params: vec![Param {
+                        key: "factor".into(),
+                        value: "3".into(),
+                    }],
+                },
+            ],
+        };
+        let results = examples.run(&SOLUTION, &dir).unwrap();
+        let passes: Vec<(&str, u8, bool)> = results
+            .iter()
+            .map(|r| (r.file.as_str(), r.part, r.is_pass()))
+            .collect();
+        assert_eq!(
+            passes,
+            vec![
+                ("a.txt", 1, true),
+                ("a.txt", 2, false),
+                ("b.txt", 1, true),
+                ("b.txt (factor=3)", 1, true)
+            ]
+        );
+        assert_eq!(results[1].to_string(), "Part 2: FAIL\n  x\n- z\n+ y");
+
+        let missing = Examples {
+            cases: vec![ExampleCase {
+                file: "c.txt".into(),
+                part_1: None,
+                part_2: None,
+                params: vec![],
+            }],
+        };
+        assert!(missing.run(&SOLUTION, &dir).is_err());
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn describes_failures() {
+        let result = CaseResult {
+            file: "01.txt".into(),
+            part: 1,
+            expected: "31".into(),
+            actual: Some("30".into()),
+        };
+        assert_eq!(result.to_string(), "Part 1: FAIL (expected 31, got 30)");
+
+        let result = CaseResult {
+            actual: None,
+            ..result
+        };
+        assert_eq!(
+            result.to_string(),
+            "Part 1: FAIL (expected 31, got nothing)"
+        );
+    }
+}
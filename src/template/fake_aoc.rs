@@ -0,0 +1,287 @@
+/// A local stand-in for Advent of Code, so the download, read and submit flow can be used and tested offline.
+use std::{
+    fs,
+    path::PathBuf,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use crate::template::{
+    answers::Answers,
+    aoc_client::{write_creating_dirs, AocClient, AocClientError, SubmitOutcome},
+    Puzzle,
+};
+
+/// How long the site makes you wait after a wrong answer, at first.
+const DEFAULT_COOLDOWN: Duration = Duration::from_secs(60);
+
+/// Serves a day's input and puzzle from a fixtures directory, and checks submissions against known answers:
+///  - `answers.json`: the expected answers, in the same format as `data/answers.json`.
+///  - `inputs/NN.txt` and `puzzles/NN.md`: what is handed out by `download` and `read`.
+///
+/// Fixtures for years other than the home year live in a `<year>` subdirectory, like the data folder.
+///
+/// Replies to submissions are worded like the site's. After a wrong answer, further submissions are
+/// answered with "wait" until the cooldown has passed, which is remembered in `.cooldown` next to the fixtures.
+pub struct FakeAoc {
+    fixtures: PathBuf,
+    data_dir: PathBuf,
+    cooldown: Duration,
+}
+
+impl FakeAoc {
+    pub fn new(fixtures: impl Into<PathBuf>) -> Self {
+        Self {
+            fixtures: fixtures.into(),
+            data_dir: PathBuf::from("data"),
+            cooldown: DEFAULT_COOLDOWN,
+        }
+    }
+
+    /// Set where downloads are written to, `data` by default.
+    #[must_use]
+    pub fn with_data_dir(mut self, data_dir: impl Into<PathBuf>) -> Self {
+        self.data_dir = data_dir.into();
+        self
+    }
+
+    /// Set how long to refuse submissions after a wrong answer.
+    #[must_use]
+    pub fn with_cooldown(mut self, cooldown: Duration) -> Self {
+        self.cooldown = cooldown;
+        self
+    }
+
+    fn fixture(
+        &self,
+        folder: &str,
+        puzzle: Puzzle,
+        extension: &str,
+    ) -> Result<String, AocClientError> {
+        let path = puzzle.data_file(&self.fixtures, folder, extension);
+        fs::read_to_string(&path).map_err(|e| {
+            AocClientError::Failed(format!(
+                "could not read fixture \"{}\": {e}",
+                path.display()
+            ))
+        })
+    }
+
+    fn cooldown_path(&self) -> PathBuf {
+        self.fixtures.join(".cooldown")
+    }
+
+    /// Seconds left until submissions are accepted again, if any.
+    fn cooldown_left(&self) -> Option<u64> {
+        let until: u64 = fs::read_to_string(self.cooldown_path())
+            .ok()?
+            .trim()
+            .parse()
+            .ok()?;
+        until.checked_sub(unix_now()).filter(|left| *left > 0)
+    }
+
+    fn start_cooldown(&self) -> Result<(), AocClientError> {
+        let until = unix_now() + self.cooldown.as_secs();
+        fs::write(self.cooldown_path(), until.to_string())
+            .map_err(|e| AocClientError::Failed(format!("could not store cooldown: {e}")))
+    }
+
+    /// The reply the site would give to a submission, in its own words.
+    fn reply(&self, puzzle: Puzzle, part: u8, answer: &str) -> Result<String, AocClientError> {
+        if let Some(left) = self.cooldown_left() {
+            return Ok(format!("You gave an answer too recently; you have to wait after submitting an answer before trying again.  You have {}m {}s left to wait.", left / 60, left % 60));
+        }
+
+        let answers =
+            Answers::read_from_path(&puzzle.year.data_dir(&self.fixtures).join("answers.json"))
+                .map_err(|e| {
+                    AocClientError::Failed(format!("could not read expected answers: {e}"))
+                })?;
+
+        let Some(expected) = answers.get(puzzle.day, None, part) else {
+            return Ok(
+                "You don't seem to be solving the right level.  Did you already complete it?"
+                    .into(),
+            );
+        };
+
+        if expected == answer {
+            return Ok("That's the right answer!".into());
+        }
+
+        self.start_cooldown()?;
+
+        let hint = match (answer.parse::<i128>(), expected.parse::<i128>()) {
+            (Ok(actual), Ok(expected)) if actual > expected => "; your answer is too high",
+            (Ok(actual), Ok(expected)) if actual < expected => "; your answer is too low",
+            _ => "",
+        };
+        Ok(format!("That's not the right answer{hint}."))
+    }
+}
+
+impl AocClient for FakeAoc {
+    fn name(&self) -> &'static str {
+        "the offline stand-in"
+    }
+
+    fn check(&self) -> Result<(), AocClientError> {
+        if self.fixtures.is_dir() {
+            Ok(())
+        } else {
+            Err(AocClientError::Unavailable(format!(
+                "fixtures directory \"{}\" does not exist.",
+                self.fixtures.display()
+            )))
+        }
+    }
+
+    fn read(&self, puzzle: Puzzle) -> Result<(), AocClientError> {
+        println!("{}", self.fixture("puzzles", puzzle, "md")?);
+        Ok(())
+    }
+
+    fn download(&self, puzzle: Puzzle) -> Result<(), AocClientError> {
+        let input = self.fixture("inputs", puzzle, "txt")?;
+        let description = self.fixture("puzzles", puzzle, "md")?;
+
+        let input_path = puzzle.data_file(&self.data_dir, "inputs", "txt");
+        let puzzle_path = puzzle.data_file(&self.data_dir, "puzzles", "md");
+        write_creating_dirs(&input_path, &input)?;
+        write_creating_dirs(&puzzle_path, &description)?;
+
+        println!(
+            "🎄 Successfully wrote input to \"{}\".",
+            input_path.display()
+        );
+        println!(
+            "🎄 Successfully wrote puzzle to \"{}\".",
+            puzzle_path.display()
+        );
+        Ok(())
+    }
+
+    fn submit(
+        &self,
+        puzzle: Puzzle,
+        part: u8,
+        answer: &str,
+    ) -> Result<SubmitOutcome, AocClientError> {
+        let reply = self.reply(puzzle, part, answer)?;
+        eprintln!("{reply}");
+        Ok(SubmitOutcome::from_reply(&reply))
+    }
+}
+
+fn unix_now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |d| d.as_secs())
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::{env, fs, path::PathBuf, process, time::Duration};
+
+    use super::FakeAoc;
+    use crate::{
+        day,
+        template::aoc_client::{AnswerHint, AocClient, SubmitOutcome},
+    };
+
+    /// A fresh fixtures directory, with a known answer for day 1 part 1 only.
+    fn fixtures(name: &str) -> PathBuf {
+        let dir = env::temp_dir().join(format!("aoc-fake-{name}-{}", process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(dir.join("inputs")).unwrap();
+        fs::create_dir_all(dir.join("puzzles")).unwrap();
+        fs::write(
+            dir.join("answers.json"),
+            r#"{ "data": [{ "day": "01", "part_1": "42", "part_2": null }] }"#,
+        )
+        .unwrap();
+        fs::write(dir.join("inputs").join("01.txt"), "1 2 3\n").unwrap();
+        fs::write(dir.join("puzzles").join("01.md"), "# Day 1\n").unwrap();
+        dir
+    }
+
+    #[test]
+    fn replies_like_the_site() {
+        let dir = fixtures("replies");
+        let aoc = FakeAoc::new(&dir).with_cooldown(Duration::ZERO);
+
+        assert_eq!(
+            aoc.submit(day!(1).into(), 1, "42").unwrap(),
+            SubmitOutcome::Correct
+        );
+        assert_eq!(
+            aoc.submit(day!(1).into(), 1, "50").unwrap(),
+            SubmitOutcome::Incorrect {
+                hint: Some(AnswerHint::TooHigh)
+            }
+        );
+        assert_eq!(
+            aoc.submit(day!(1).into(), 1, "7").unwrap(),
+            SubmitOutcome::Incorrect {
+                hint: Some(AnswerHint::TooLow)
+            }
+        );
+        assert_eq!(
+            aoc.submit(day!(1).into(), 1, "abc").unwrap(),
+            SubmitOutcome::Incorrect { hint: None }
+        );
+        assert_eq!(
+            aoc.submit(day!(1).into(), 2, "1").unwrap(),
+            SubmitOutcome::WrongLevel
+        );
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn waits_after_wrong_answers() {
+        let dir = fixtures("cooldown");
+        let aoc = FakeAoc::new(&dir).with_cooldown(Duration::from_secs(300));
+
+        assert!(matches!(
+            aoc.submit(day!(1).into(), 1, "1").unwrap(),
+            SubmitOutcome::Incorrect { .. }
+        ));
+
+        // the cooldown outlives the client, like it would on the site.
+        let aoc = FakeAoc::new(&dir);
+        assert!(matches!(
+            aoc.submit(day!(1).into(), 1, "42").unwrap(),
+            SubmitOutcome::Wait { seconds: Some(s) } if s > 290 && s <= 300
+        ));
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn downloads_fixtures() {
+        let dir = fixtures("download");
+        let data_dir = dir.join("data");
+        let aoc = FakeAoc::new(&dir).with_data_dir(&data_dir);
+
+        aoc.check().unwrap();
+        aoc.download(day!(1).into()).unwrap();
+        assert_eq!(
+            fs::read_to_string(data_dir.join("inputs").join("01.txt")).unwrap(),
+            "1 2 3\n"
+        );
+        assert_eq!(
+            fs::read_to_string(data_dir.join("puzzles").join("01.md")).unwrap(),
+            "# Day 1\n"
+        );
+        assert!(aoc.download(day!(2).into()).is_err());
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn is_unavailable_without_fixtures() {
+        let aoc = FakeAoc::new(env::temp_dir().join("aoc-fake-does-not-exist"));
+        assert!(aoc.check().is_err());
+    }
+}
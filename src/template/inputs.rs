@@ -0,0 +1,170 @@
+//! The inputs a solution can be run on. Besides your own input, a day can have any number of named inputs,
+//! e.g. a teammate's input or a generated stress input, at `data/inputs/NN/<name>.txt`.
+use std::{
+    env,
+    fmt::Display,
+    fs,
+    path::{Path, PathBuf},
+    process,
+};
+
+use crate::template::Puzzle;
+
+/// An input to run a solution on.
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Input {
+    /// The input handed out by the site, `data/inputs/NN.txt`.
+    Own,
+    /// An input at `data/inputs/NN/<name>.txt`, which can have its own recorded answers.
+    Named(String),
+    /// Any other file.
+    File(PathBuf),
+}
+
+impl Input {
+    /// Parse the argument of `--input`: a path if it contains a path separator or an extension,
+    /// the name of an input in `data/inputs/NN/` otherwise.
+    pub fn parse(arg: &str) -> Self {
+        if arg.contains(['/', '\\', '.']) {
+            Self::File(arg.into())
+        } else {
+            Self::Named(arg.into())
+        }
+    }
+
+    /// Parse the `--input` argument passed to the binary, defaulting to [`Input::Own`].
+    pub fn from_args() -> Self {
+        let args: Vec<String> = env::args().collect();
+
+        let Some(input_index) = args.iter().position(|x| x == "--input") else {
+            return Self::Own;
+        };
+
+        match args.get(input_index + 1) {
+            Some(arg) if !arg.starts_with("--") => Self::parse(arg),
+            _ => {
+                eprintln!("Unexpected command-line input. Format: --input <name|path>");
+                process::exit(1);
+            }
+        }
+    }
+
+    /// The name answers for this input are recorded under, if it is a named input.
+    pub fn name(&self) -> Option<&str> {
+        match self {
+            Self::Named(name) => Some(name),
+            Self::Own | Self::File(_) => None,
+        }
+    }
+
+    pub fn path(&self, puzzle: Puzzle) -> PathBuf {
+        self.path_in("data", puzzle)
+    }
+
+    fn path_in(&self, root: impl AsRef<Path>, puzzle: Puzzle) -> PathBuf {
+        match self {
+            Self::Own => puzzle.data_file(root, "inputs", "txt"),
+            Self::Named(name) => named_dir(root, puzzle).join(format!("{name}.txt")),
+            Self::File(path) => path.clone(),
+        }
+    }
+
+    pub fn read(&self, puzzle: Puzzle) -> Result<String, String> {
+        let path = self.path(puzzle);
+        fs::read_to_string(&path)
+            .map_err(|_| format!("Could not read input file \"{}\".", path.display()))
+    }
+
+    /// The named inputs of a puzzle, sorted by name.
+    pub fn list_named(puzzle: Puzzle) -> Vec<Self> {
+        Self::list_named_in("data", puzzle)
+    }
+
+    fn list_named_in(root: impl AsRef<Path>, puzzle: Puzzle) -> Vec<Self> {
+        let Ok(entries) = fs::read_dir(named_dir(root, puzzle)) else {
+            return vec![];
+        };
+
+        let mut inputs: Vec<Self> = entries
+            .filter_map(Result::ok)
+            .map(|entry| entry.path())
+            .filter(|path| path.extension().is_some_and(|ext| ext == "txt"))
+            .filter_map(|path| Some(Self::Named(path.file_stem()?.to_str()?.into())))
+            .collect();
+
+        inputs.sort_unstable();
+        inputs
+    }
+}
+
+/// `data/inputs/NN` for the home year, `data/<year>/inputs/NN` for any other year.
+fn named_dir(root: impl AsRef<Path>, puzzle: Puzzle) -> PathBuf {
+    puzzle
+        .year
+        .data_dir(root)
+        .join("inputs")
+        .join(puzzle.day.to_string())
+}
+
+impl Display for Input {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Own => write!(f, "own input"),
+            Self::Named(name) => write!(f, "input \"{name}\""),
+            Self::File(path) => write!(f, "input \"{}\"", path.display()),
+        }
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::{env, fs, path::Path, process};
+
+    use super::Input;
+    use crate::{day, template::Puzzle};
+
+    #[test]
+    fn parses_names_and_paths() {
+        assert_eq!(Input::parse("alice"), Input::Named("alice".into()));
+        assert_eq!(Input::parse("stress.txt"), Input::File("stress.txt".into()));
+        assert_eq!(
+            Input::parse("data/big/01"),
+            Input::File("data/big/01".into())
+        );
+    }
+
+    #[test]
+    fn locates_inputs() {
+        let puzzle = Puzzle::from(day!(3));
+        let inputs = Path::new("data").join("inputs");
+        assert_eq!(Input::Own.path(puzzle), inputs.join("03.txt"));
+        assert_eq!(
+            Input::Named("alice".into()).path(puzzle),
+            inputs.join("03").join("alice.txt")
+        );
+        assert_eq!(
+            Input::File("x/y.txt".into()).path(puzzle),
+            Path::new("x/y.txt")
+        );
+    }
+
+    #[test]
+    fn lists_named_inputs() {
+        let root = env::temp_dir().join(format!("aoc-inputs-{}", process::id()));
+        let dir = root.join("inputs").join("03");
+        fs::create_dir_all(&dir).unwrap();
+        for file in ["bob.txt", "alice.txt", "notes.md"] {
+            fs::write(dir.join(file), "").unwrap();
+        }
+
+        assert_eq!(
+            Input::list_named_in(&root, day!(3).into()),
+            vec![Input::Named("alice".into()), Input::Named("bob".into())]
+        );
+        assert_eq!(Input::list_named_in(&root, day!(4).into()), vec![]);
+
+        fs::remove_dir_all(root).unwrap();
+    }
+}
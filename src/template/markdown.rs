@@ -0,0 +1,170 @@
+//! Converts the HTML served by Advent of Code to markdown.
+//! Only covers the handful of tags that appear in puzzle descriptions and replies to submissions.
+
+/// Convert a puzzle page to markdown, keeping only the descriptions of its parts.
+pub fn puzzle_to_markdown(html: &str) -> String {
+    let parts: Vec<String> = articles(html)
+        .filter(|(tag, _)| tag.contains("day-desc"))
+        .map(|(_, body)| to_markdown(body))
+        .collect();
+
+    parts.join("\n\n") + "\n"
+}
+
+/// Convert the page the site replies with after a submission to markdown, keeping only the message.
+pub fn reply_to_markdown(html: &str) -> String {
+    articles(html)
+        .next()
+        .map_or_else(|| to_markdown(html), |(_, body)| to_markdown(body))
+}
+
+/// Iterate over the `<article>` elements of a page, as pairs of opening tag and inner HTML.
+fn articles(html: &str) -> impl Iterator<Item = (&str, &str)> {
+    let mut rest = html;
+
+    std::iter::from_fn(move || {
+        let start = rest.find("<article")?;
+        let tag_end = start + rest[start..].find('>')?;
+        let end = tag_end + rest[tag_end..].find("</article>")?;
+
+        let article = (&rest[start..=tag_end], &rest[tag_end + 1..end]);
+        rest = &rest[end + "</article>".len()..];
+        Some(article)
+    })
+}
+
+fn to_markdown(html: &str) -> String {
+    let mut markdown = String::with_capacity(html.len());
+    let mut links: Vec<String> = vec![];
+    let mut in_pre = false;
+    let mut in_code = false;
+    let mut rest = html;
+
+    while !rest.is_empty() {
+        let Some(after) = rest.strip_prefix('<') else {
+            let end = rest.find('<').unwrap_or(rest.len());
+            markdown.push_str(&decode_entities(&rest[..end]));
+            rest = &rest[end..];
+            continue;
+        };
+
+        let Some(end) = after.find('>') else {
+            break;
+        };
+        let tag = &after[..end];
+        rest = &after[end + 1..];
+
+        let (name, attributes) = tag.split_once(char::is_whitespace).unwrap_or((tag, ""));
+
+        match name {
+            "h2" => markdown.push_str("## "),
+            "/h2" | "/p" | "/ul" => markdown.push_str("\n\n"),
+            "pre" => {
+                in_pre = true;
+                markdown.push_str("```\n");
+            }
+            "/pre" => {
+                in_pre = false;
+                if !markdown.ends_with('\n') {
+                    markdown.push('\n');
+                }
+                markdown.push_str("```\n\n");
+            }
+            "code" if !in_pre => {
+                in_code = true;
+                markdown.push('`');
+            }
+            "/code" if !in_pre => {
+                in_code = false;
+                markdown.push('`');
+            }
+            // emphasis is not rendered inside code, so it is dropped there.
+            "em" | "/em" if !in_pre && !in_code => markdown.push('*'),
+            "li" => markdown.push_str("- "),
+            "/li" => markdown.push('\n'),
+            "a" => {
+                links.push(attribute(attributes, "href").unwrap_or_default().into());
+                markdown.push('[');
+            }
+            "/a" => {
+                let href = links.pop().unwrap_or_default();
+                markdown.push_str(&format!("]({href})"));
+            }
+            _ => {}
+        }
+    }
+
+    collapse_blank_lines(markdown.trim())
+}
+
+fn attribute<'a>(attributes: &'a str, name: &str) -> Option<&'a str> {
+    let start = attributes.find(&format!("{name}=\""))? + name.len() + 2;
+    let end = start + attributes[start..].find('"')?;
+    Some(&attributes[start..end])
+}
+
+fn decode_entities(text: &str) -> String {
+    text.replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&#39;", "'")
+        .replace("&apos;", "'")
+        .replace("&nbsp;", " ")
+        .replace("&amp;", "&")
+}
+
+/// Collapse runs of blank lines left behind by whitespace between tags into a single one.
+fn collapse_blank_lines(markdown: &str) -> String {
+    let mut collapsed = String::with_capacity(markdown.len());
+    let mut newlines = 0;
+
+    for c in markdown.chars() {
+        if c == '\n' {
+            newlines += 1;
+            if newlines > 2 {
+                continue;
+            }
+        } else {
+            newlines = 0;
+        }
+        collapsed.push(c);
+    }
+
+    collapsed
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{puzzle_to_markdown, reply_to_markdown};
+
+    #[test]
+    fn converts_puzzle_pages() {
+        let html = r#"<html><body><main>
+<article class="day-desc"><h2>--- Day 1: Test ---</h2><p>The <em>answer</em> is in <a href="/2024/about">the list</a> &amp; more:</p>
+<pre><code>3   4
+<em>4</em>   3
+</code></pre>
+<ul>
+<li>What is <code><em>x</em> &lt; y</code>?</li>
+</ul>
+</article>
+<p>Your puzzle answer was <code>11</code>.</p>
+<article class="day-desc"><h2 id="part2">--- Part Two ---</h2><p>Again.</p>
+</article>
+</main></body></html>"#;
+
+        assert_eq!(
+            puzzle_to_markdown(html),
+            "## --- Day 1: Test ---\n\nThe *answer* is in [the list](/2024/about) & more:\n\n```\n3   4\n4   3\n```\n\n- What is `x < y`?\n\n## --- Part Two ---\n\nAgain.\n"
+        );
+    }
+
+    #[test]
+    fn converts_replies() {
+        let html = r#"<main><article><p>That's not the right answer; your answer is too low. <a href="/2024/day/1">[Return to Day 1]</a></p></article></main>"#;
+        assert_eq!(
+            reply_to_markdown(html),
+            "That's not the right answer; your answer is too low. [[Return to Day 1]](/2024/day/1)"
+        );
+    }
+}
@@ -1,68 +1,146 @@
 use std::{env, fs};
 
 pub mod aoc_cli;
+pub mod aoc_client;
+pub mod aoc_http;
+pub mod bench;
 pub mod commands;
+pub mod examples;
+pub mod fake_aoc;
+pub mod inputs;
+pub mod params;
+pub mod registry;
+pub mod replay;
+pub mod report;
 pub mod runner;
+pub mod visualize;
 
 pub use day::*;
+pub use parse_error::*;
+pub use year::*;
 
+mod answers;
+mod compare;
 mod day;
+mod markdown;
+mod parse_error;
+mod puzzle_examples;
 mod readme_benchmarks;
 mod run_multi;
+mod status;
+mod submissions;
 mod timings;
+mod watch;
+mod year;
 
 pub const ANSI_ITALIC: &str = "\x1b[3m";
 pub const ANSI_BOLD: &str = "\x1b[1m";
 pub const ANSI_RESET: &str = "\x1b[0m";
 
 /// Helper function that reads a text file to a string.
+/// Pass a [`Day`] for the home year, or a [`Puzzle`] for any year.
 #[must_use]
-pub fn read_file(folder: &str, day: Day) -> String {
+pub fn read_file(folder: &str, puzzle: impl Into<Puzzle>) -> String {
     let cwd = env::current_dir().unwrap();
-    let filepath = cwd.join("data").join(folder).join(format!("{day}.txt"));
+    let filepath = puzzle.into().data_file(cwd.join("data"), folder, "txt");
     let f = fs::read_to_string(filepath);
     f.expect("could not open input file")
 }
 
 /// Helper function that reads a text file to string, appending a part suffix. E.g. like `01-2.txt`.
 #[must_use]
-pub fn read_file_part(folder: &str, day: Day, part: u8) -> String {
+pub fn read_file_part(folder: &str, puzzle: impl Into<Puzzle>, part: u8) -> String {
     let cwd = env::current_dir().unwrap();
-    let filepath = cwd
-        .join("data")
+    let puzzle = puzzle.into();
+    let filepath = puzzle
+        .year
+        .data_dir(cwd.join("data"))
         .join(folder)
-        .join(format!("{day}-{part}.txt"));
+        .join(format!("{}-{part}.txt", puzzle.day));
     let f = fs::read_to_string(filepath);
     f.expect("could not open input file")
 }
 
-/// Creates the constant `DAY` and sets up the input and runner for each part.
+/// Creates the constants `DAY` and `PUZZLE` and sets up the input and runner for each part.
 ///
-/// The optional, second parameter (1 or 2) allows you to only run a single part of the solution.
+/// Solutions for the home year pass just the day, solutions for other years pass it as `year: 2023, 1`.
+/// The optional parameter (1 or 2) after the day allows you to only run a single part of the solution.
+/// Solutions with parameters pass their type last, e.g. `solution!(11, params: Params)`, and their parts
+/// take `(input, &Params)`, see [`params`].
 #[macro_export]
 macro_rules! solution {
-    ($day:expr) => {
-        $crate::solution!(@impl $day, [part_one, 1] [part_two, 2]);
+    (year: $year:expr, $day:expr $(, $part:tt)?) => {
+        $crate::solution!(@parts $crate::year!($year), $day, [$($part)?], ());
     };
-    ($day:expr, 1) => {
-        $crate::solution!(@impl $day, [part_one, 1]);
+    (year: $year:expr, $day:expr, params: $params:ty) => {
+        $crate::solution!(@parts $crate::year!($year), $day, [], $params);
     };
-    ($day:expr, 2) => {
-        $crate::solution!(@impl $day, [part_two, 2]);
+    (year: $year:expr, $day:expr, $part:tt, params: $params:ty) => {
+        $crate::solution!(@parts $crate::year!($year), $day, [$part], $params);
+    };
+    ($day:expr $(, $part:tt)?) => {
+        $crate::solution!(@parts $crate::template::Year::home(), $day, [$($part)?], ());
+    };
+    ($day:expr, params: $params:ty) => {
+        $crate::solution!(@parts $crate::template::Year::home(), $day, [], $params);
+    };
+    ($day:expr, $part:tt, params: $params:ty) => {
+        $crate::solution!(@parts $crate::template::Year::home(), $day, [$part], $params);
+    };
+
+    (@parts $year:expr, $day:expr, [], $params:tt) => {
+        $crate::solution!(@params $year, $day, $params, [part_one, 1] [part_two, 2]);
+    };
+    (@parts $year:expr, $day:expr, [1], $params:tt) => {
+        $crate::solution!(@params $year, $day, $params, [part_one, 1]);
+    };
+    (@parts $year:expr, $day:expr, [2], $params:tt) => {
+        $crate::solution!(@params $year, $day, $params, [part_two, 2]);
     };
 
-    (@impl $day:expr, $( [$func:expr, $part:expr] )*) => {
+    // parts without parameters ignore the `()` they are passed.
+    (@params $year:expr, $day:expr, (), $( [$func:ident, $part:tt] )*) => {
+        $crate::solution!(@impl $year, $day, (), $( [|input: &str, _: &()| $func(input), $part] )*);
+    };
+    (@params $year:expr, $day:expr, $params:ty, $( [$func:ident, $part:tt] )*) => {
+        $crate::solution!(@impl $year, $day, $params, $( [|input: &str, params: &$params| $func(input, params), $part] )*);
+    };
+
+    (@impl $year:expr, $day:expr, $params:ty, $( [$func:expr, $part:expr] )*) => {
         /// The current day.
         const DAY: $crate::template::Day = $crate::day!($day);
 
-        #[cfg(feature = "dhat-heap")]
-        #[global_allocator]
-        static ALLOC: dhat::Alloc = dhat::Alloc;
+        /// The current puzzle, i.e. the year and day.
+        const PUZZLE: $crate::template::Puzzle = $crate::template::Puzzle::new($year, DAY);
+
+        /// The entry point for this day in the in-process solution registry.
+        pub const SOLUTION: $crate::template::registry::Solution =
+            $crate::template::registry::Solution {
+                puzzle: PUZZLE,
+                parts: &[$(
+                    $crate::template::registry::SolutionPart {
+                        part: $part,
+                        run: |input, params, bench, show_progress| {
+                            $crate::template::runner::solve_part_with_params::<$params, _>(
+                                $func, input, params, PUZZLE, $part, bench, show_progress,
+                            )
+                        },
+                    },
+                )*],
+            };
+
+        /// Runs the cases listed in `data/examples/NN.json`, if there is one.
+        #[cfg(test)]
+        #[test]
+        fn example_cases() {
+            $crate::template::examples::assert_examples(&SOLUTION);
+        }
 
+        // unused when the solution is compiled into the registry.
+        #[allow(dead_code)]
         fn main() {
-            use $crate::template::runner::*;
-            let input = $crate::template::read_file("inputs", DAY);
-            $( run_part($func, &input, DAY, $part); )*
+            let input = $crate::template::inputs::Input::from_args();
+            $crate::template::runner::run_solution(&SOLUTION, &input);
         }
     };
 }
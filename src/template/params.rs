@@ -0,0 +1,183 @@
+//! Parameters of a solution that differ between the examples and the real input, e.g. a grid size.
+//!
+//! A solution declares them with the [`params!`](crate::params) macro and passes the struct to
+//! `solution!(11, params: Params)`, which makes its parts take `(input, &Params)`.
+//! The defaults are used for the real input, and can be overridden with `--param key=value`
+//! or per case in the examples manifest.
+use std::{env, fmt::Display, str::FromStr};
+
+/// The parameters of a solution. Implemented by the [`params!`](crate::params) macro.
+pub trait Params: Default {
+    /// Set the parameter `key` from its text value.
+    fn set(&mut self, key: &str, value: &str) -> Result<(), String>;
+
+    /// The defaults with `overrides` applied in order.
+    fn with_overrides(overrides: &[Param]) -> Result<Self, String> {
+        let mut params = Self::default();
+        for param in overrides {
+            params.set(&param.key, &param.value)?;
+        }
+        Ok(params)
+    }
+}
+
+/// Solutions without parameters.
+impl Params for () {
+    fn set(&mut self, key: &str, _: &str) -> Result<(), String> {
+        Err(format!(
+            "unknown parameter `{key}`, this solution has no parameters"
+        ))
+    }
+}
+
+/// An override of a parameter, written as `key=value`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Param {
+    pub key: String,
+    pub value: String,
+}
+
+impl Param {
+    /// Every `--param key=value` passed to the binary.
+    pub fn from_args() -> Result<Vec<Self>, String> {
+        let args: Vec<String> = env::args().collect();
+
+        args.iter()
+            .enumerate()
+            .filter(|(_, arg)| *arg == "--param")
+            .map(|(index, _)| {
+                args.get(index + 1)
+                    .ok_or("Unexpected command-line input. Format: --param <key=value>")?
+                    .parse()
+            })
+            .collect()
+    }
+}
+
+impl FromStr for Param {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.split_once('=') {
+            Some((key, value)) if !key.is_empty() => Ok(Self {
+                key: key.into(),
+                value: value.into(),
+            }),
+            _ => Err(format!("expected a parameter as `key=value`, got `{s}`")),
+        }
+    }
+}
+
+impl Display for Param {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}={}", self.key, self.value)
+    }
+}
+
+/// Declares the parameters of a solution with their defaults for the real input:
+///
+/// ```ignore
+/// advent_of_code::params! {
+///     pub struct Params {
+///         blinks: u64 = 25,
+///     }
+/// }
+/// ```
+///
+/// Every field can be set from text with its `FromStr` implementation.
+#[macro_export]
+macro_rules! params {
+    (
+        $(#[$meta:meta])*
+        $vis:vis struct $name:ident {
+            $( $(#[$field_meta:meta])* $field:ident : $ty:ty = $default:expr ),* $(,)?
+        }
+    ) => {
+        $(#[$meta])*
+        #[derive(Clone, Debug, PartialEq)]
+        $vis struct $name {
+            $( $(#[$field_meta])* pub $field: $ty, )*
+        }
+
+        impl Default for $name {
+            fn default() -> Self {
+                Self { $( $field: $default, )* }
+            }
+        }
+
+        impl $crate::template::params::Params for $name {
+            fn set(&mut self, key: &str, value: &str) -> Result<(), String> {
+                match key {
+                    $(
+                        stringify!($field) => {
+                            self.$field = value.parse().map_err(|e| {
+                                format!("invalid value `{value}` for parameter `{key}`: {e}")
+                            })?;
+                        }
+                    )*
+                    _ => {
+                        let keys: &[&str] = &[$(stringify!($field)),*];
+                        return Err(format!(
+                            "unknown parameter `{key}`, expected one of: {}",
+                            keys.join(", ")
+                        ));
+                    }
+                }
+                Ok(())
+            }
+        }
+    };
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{Param, Params};
+
+    crate::params! {
+        struct Grid {
+            width: usize = 101,
+            debug: bool = false,
+        }
+    }
+
+    fn param(s: &str) -> Param {
+        s.parse().unwrap()
+    }
+
+    #[test]
+    fn parses_overrides() {
+        assert_eq!(
+            param("width=11"),
+            Param {
+                key: "width".into(),
+                value: "11".into()
+            }
+        );
+        assert_eq!(param("title=a=b").value, "a=b");
+        assert!("width".parse::<Param>().is_err());
+        assert!("=11".parse::<Param>().is_err());
+    }
+
+    #[test]
+    fn applies_overrides_to_defaults() {
+        assert_eq!(Grid::with_overrides(&[]).unwrap().width, 101);
+        assert_eq!(
+            Grid::with_overrides(&[param("width=11"), param("debug=true")]),
+            Ok(Grid {
+                width: 11,
+                debug: true
+            })
+        );
+        assert_eq!(
+            Grid::with_overrides(&[param("height=7")]),
+            Err("unknown parameter `height`, expected one of: width, debug".into())
+        );
+        assert_eq!(
+            Grid::with_overrides(&[param("width=wide")]),
+            Err("invalid value `wide` for parameter `width`: invalid digit found in string".into())
+        );
+        assert!(<()>::with_overrides(&[param("width=11")]).is_err());
+    }
+}
@@ -0,0 +1,120 @@
+//! An error for solutions that fail to parse their input, pointing at where it went wrong.
+
+use std::{error::Error, fmt::Display, num::ParseIntError};
+
+/// Why an input could not be parsed. The line and column are 1-based and are shown when known,
+/// e.g. `line 3, column 5: invalid digit`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ParseError {
+    pub message: String,
+    pub line: Option<usize>,
+    pub column: Option<usize>,
+}
+
+impl ParseError {
+    pub fn new(message: impl Into<String>) -> Self {
+        Self {
+            message: message.into(),
+            line: None,
+            column: None,
+        }
+    }
+
+    #[must_use]
+    pub fn at_line(mut self, line: usize) -> Self {
+        self.line = Some(line);
+        self
+    }
+
+    #[must_use]
+    pub fn at_column(mut self, column: usize) -> Self {
+        self.column = Some(column);
+        self
+    }
+
+    /// Move the column by `offset` characters, for errors from parsing a slice that starts there.
+    /// Errors without a column start at the slice.
+    #[must_use]
+    pub fn offset_by(mut self, offset: usize) -> Self {
+        self.column = Some(self.column.unwrap_or(1) + offset);
+        self
+    }
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match (self.line, self.column) {
+            (Some(line), Some(column)) => write!(f, "line {line}, column {column}: ")?,
+            (Some(line), None) => write!(f, "line {line}: ")?,
+            (None, Some(column)) => write!(f, "column {column}: ")?,
+            (None, None) => {}
+        }
+        write!(f, "{}", self.message)
+    }
+}
+
+impl Error for ParseError {}
+
+impl From<String> for ParseError {
+    fn from(value: String) -> Self {
+        Self::new(value)
+    }
+}
+
+impl From<&str> for ParseError {
+    fn from(value: &str) -> Self {
+        Self::new(value)
+    }
+}
+
+impl From<ParseIntError> for ParseError {
+    fn from(value: ParseIntError) -> Self {
+        Self::new(value.to_string())
+    }
+}
+
+/// Parse every line of an input, stopping at the first error and adding its line number to it.
+pub fn parse_lines<T, E: Into<ParseError>>(
+    input: &str,
+    parse: impl Fn(&str) -> Result<T, E>,
+) -> Result<Vec<T>, ParseError> {
+    input
+        .lines()
+        .enumerate()
+        .map(|(index, line)| parse(line).map_err(|e| e.into().at_line(index + 1)))
+        .collect()
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{parse_lines, ParseError};
+
+    #[test]
+    fn shows_where_it_went_wrong() {
+        assert_eq!(ParseError::new("oops").to_string(), "oops");
+        assert_eq!(
+            ParseError::new("oops").at_line(3).to_string(),
+            "line 3: oops"
+        );
+        assert_eq!(
+            ParseError::new("oops")
+                .at_column(2)
+                .offset_by(4)
+                .at_line(3)
+                .to_string(),
+            "line 3, column 6: oops"
+        );
+    }
+
+    #[test]
+    fn adds_line_numbers() {
+        let parsed = parse_lines("1\n2\nx\n4", str::parse::<u64>);
+        assert_eq!(
+            parsed.unwrap_err().to_string(),
+            "line 3: invalid digit found in string"
+        );
+        assert_eq!(parse_lines("1\n2", str::parse::<u64>), Ok(vec![1, 2]));
+    }
+}
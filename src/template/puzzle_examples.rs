@@ -0,0 +1,303 @@
+//! Guesses a puzzle's examples and their answers from its markdown description, `data/puzzles/NN.md`.
+//!
+//! Puzzles introduce their main example with "For example" and highlight the answer it gives,
+//! e.g. "In this example, the total is `11`." The guesses lean on these habits.
+
+/// A fenced code block in the description.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CodeBlock {
+    /// The part whose description contains the block.
+    pub part: u8,
+    pub content: String,
+    /// Whether the text right before the block mentions an example.
+    pub introduced_as_example: bool,
+}
+
+impl CodeBlock {
+    /// The first line, shortened, to tell blocks apart in a list.
+    pub fn preview(&self) -> String {
+        let first_line = self.content.lines().next().unwrap_or_default();
+        let mut preview: String = first_line.chars().take(40).collect();
+        if preview.len() < first_line.len() || self.content.lines().count() > 1 {
+            preview.push_str(" …");
+        }
+        preview
+    }
+}
+
+/// What could be found in a description.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct PuzzleExamples {
+    pub blocks: Vec<CodeBlock>,
+    /// The highlighted answer the example of each part gives, if any.
+    pub part_1: Option<String>,
+    pub part_2: Option<String>,
+}
+
+impl PuzzleExamples {
+    pub fn parse(markdown: &str) -> Self {
+        let sections = split_parts(markdown);
+
+        let mut blocks = vec![];
+        let mut answers = [None, None];
+
+        for (i, section) in sections.iter().enumerate() {
+            let part = u8::try_from(i + 1).unwrap_or(2);
+            blocks.extend(code_blocks(section, part));
+            answers[i] = last_numeric_code_span(section);
+        }
+
+        let [part_1, part_2] = answers;
+        Self {
+            blocks,
+            part_1,
+            part_2,
+        }
+    }
+
+    pub fn answer(&self, part: u8) -> Option<&str> {
+        match part {
+            1 => self.part_1.as_deref(),
+            2 => self.part_2.as_deref(),
+            _ => None,
+        }
+    }
+
+    /// The index of the block that is most likely the main example of a part:
+    /// the first one introduced as an example, otherwise the longest one.
+    /// Part two often reuses the example of part one, so it only has a main example of its own
+    /// if its description introduces one.
+    pub fn main_block(&self, part: u8) -> Option<usize> {
+        let in_part = || {
+            self.blocks
+                .iter()
+                .enumerate()
+                .filter(move |(_, block)| block.part == part)
+        };
+
+        let introduced = in_part().find(|(_, block)| block.introduced_as_example);
+
+        match (introduced, part) {
+            (Some((index, _)), _) => Some(index),
+            (None, 1) => in_part()
+                .max_by_key(|(i, block)| (block.content.lines().count(), std::cmp::Reverse(*i)))
+                .map(|(index, _)| index),
+            (None, _) => None,
+        }
+    }
+}
+
+/// Split a description into the sections of its parts, at the part two heading.
+fn split_parts(markdown: &str) -> Vec<&str> {
+    let mut offset = 0;
+    for line in markdown.split_inclusive('\n') {
+        if is_part_two_heading(line) {
+            return vec![&markdown[..offset], &markdown[offset..]];
+        }
+        offset += line.len();
+    }
+    vec![markdown]
+}
+
+/// Matches both `## --- Part Two ---` and the setext heading `\--- Part Two ---` that aoc-cli writes,
+/// which is underlined on the next line.
+fn is_part_two_heading(line: &str) -> bool {
+    let line = line.trim();
+    let heading = line
+        .strip_prefix('\\')
+        .or_else(|| {
+            line.strip_prefix('#')
+                .map(|rest| rest.trim_start_matches('#'))
+        })
+        .map(str::trim_start);
+    heading == Some("--- Part Two ---")
+}
+
+fn code_blocks(section: &str, part: u8) -> Vec<CodeBlock> {
+    let mut blocks = vec![];
+    let mut block: Option<Vec<&str>> = None;
+    let mut text_before = String::new();
+    let mut new_paragraph = false;
+
+    for line in section.lines() {
+        if line.trim_start().starts_with("```") {
+            match block.take() {
+                Some(lines) => {
+                    blocks.push(CodeBlock {
+                        part,
+                        content: lines.join("\n") + "\n",
+                        introduced_as_example: mentions_example(&text_before),
+                    });
+                    text_before.clear();
+                }
+                None => block = Some(vec![]),
+            }
+        } else if let Some(lines) = block.as_mut() {
+            lines.push(line);
+        } else if line.trim().is_empty() {
+            new_paragraph = true;
+        } else {
+            // only the paragraph right before a block introduces it.
+            if new_paragraph {
+                text_before.clear();
+                new_paragraph = false;
+            }
+            text_before.push_str(line);
+            text_before.push('\n');
+        }
+    }
+
+    blocks
+}
+
+fn mentions_example(text: &str) -> bool {
+    let text = text.to_lowercase();
+    text.contains("example") || text.contains("for instance")
+}
+
+/// The last inline code span holding a number, which is where puzzles put the answer to their example.
+/// Lines that state an answer already given to the site are skipped.
+fn last_numeric_code_span(section: &str) -> Option<String> {
+    let mut in_block = false;
+    let mut last = None;
+
+    for line in section.lines() {
+        if line.trim_start().starts_with("```") {
+            in_block = !in_block;
+            continue;
+        }
+        if in_block || line.starts_with("Your puzzle answer was") {
+            continue;
+        }
+
+        for span in line.split('`').skip(1).step_by(2) {
+            let span = span.trim_matches('*').trim();
+            if !span.is_empty() && span.parse::<i128>().is_ok() {
+                last = Some(span.to_string());
+            }
+        }
+    }
+
+    last
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::PuzzleExamples;
+
+    const DESCRIPTION: &str = "## --- Day 1: Test ---
+
+The lists are `2` long.
+
+```
+1 2
+```
+
+For example:
+
+```
+3   4
+4   3
+2   5
+```
+
+Pair up the numbers, e.g. `3` and `4`. In the example above, the total is `11`.
+
+What is the total?
+
+## --- Part Two ---
+
+This time, for example:
+
+```
+1
+2
+```
+
+So, the score is `31`.
+";
+
+    /// The same description as aoc-cli writes it, with setext headings.
+    const AOC_CLI_DESCRIPTION: &str = "\\--- Day 1: Test ---
+----------
+
+The lists are `2` long.
+
+```
+1 2
+```
+
+For example:
+
+```
+3   4
+4   3
+2   5
+```
+
+Pair up the numbers, e.g. `3` and `4`. In the example above, the total is `*11*`.
+
+What is the total?
+
+Your puzzle answer was `1234`.
+
+\\--- Part Two ---
+----------
+
+This time, for example:
+
+```
+1
+2
+```
+
+So, the score is `*31*`.
+";
+
+    #[test]
+    fn finds_blocks_and_answers() {
+        for description in [DESCRIPTION, AOC_CLI_DESCRIPTION] {
+            let examples = PuzzleExamples::parse(description);
+            assert_eq!(examples.blocks.len(), 3);
+            assert_eq!(examples.blocks[1].content, "3   4\n4   3\n2   5\n");
+            assert!(!examples.blocks[0].introduced_as_example);
+            assert!(examples.blocks[1].introduced_as_example);
+            assert_eq!(examples.blocks[2].part, 2);
+            assert_eq!(examples.answer(1), Some("11"));
+            assert_eq!(examples.answer(2), Some("31"));
+        }
+    }
+
+    #[test]
+    fn guesses_main_blocks() {
+        for description in [DESCRIPTION, AOC_CLI_DESCRIPTION] {
+            let examples = PuzzleExamples::parse(description);
+            assert_eq!(examples.main_block(1), Some(1));
+            assert_eq!(examples.main_block(2), Some(2));
+        }
+
+        // without an introduction, the longest block of part one wins, and part two reuses it.
+        let plain = "```\na\n```\n\n```\nb\nc\n```\n\n## --- Part Two ---\n\nNow `5`.\n";
+        let examples = PuzzleExamples::parse(plain);
+        assert_eq!(examples.main_block(1), Some(1));
+        assert_eq!(examples.main_block(2), None);
+        assert_eq!(examples.answer(1), None);
+        assert_eq!(examples.answer(2), Some("5"));
+    }
+
+    #[test]
+    fn skips_submitted_answers() {
+        let examples =
+            PuzzleExamples::parse("The answer is `7`.\n\nYour puzzle answer was `1234`.\n");
+        assert_eq!(examples.answer(1), Some("7"));
+    }
+
+    #[test]
+    fn previews_blocks() {
+        let examples = PuzzleExamples::parse(DESCRIPTION);
+        assert_eq!(examples.blocks[0].preview(), "1 2");
+        assert_eq!(examples.blocks[1].preview(), "3   4 …");
+    }
+}
@@ -0,0 +1,39 @@
+/// The in-process registry of all solutions in `src/bin`.
+/// With the `registry` feature, `build.rs` includes every solution into the library, and the `solution!` macro gives each one a [`Solution`] entry point.
+use crate::template::{bench::BenchConfig, params::Param, report::PartReport, Puzzle};
+
+/// Runs a single part on an input: `(input, params, bench, show_progress)`.
+/// See [`crate::template::runner::solve_part_with_params`] for what the arguments do.
+pub type PartRunner = fn(&str, &[Param], Option<BenchConfig>, bool) -> PartReport;
+
+#[derive(Clone, Copy)]
+pub struct SolutionPart {
+    pub part: u8,
+    pub run: PartRunner,
+}
+
+/// The entry point for a day's solution, defined by the `solution!` macro as `SOLUTION`.
+#[derive(Clone, Copy)]
+pub struct Solution {
+    pub puzzle: Puzzle,
+    pub parts: &'static [SolutionPart],
+}
+
+// the solutions are only compiled into the library with the `registry` feature, so that a day that does not compile
+// only breaks the commands that run every day. their own unit tests are run by their binaries.
+#[cfg(all(feature = "registry", not(test)))]
+mod solutions {
+    include!(concat!(env!("OUT_DIR"), "/solutions.rs"));
+}
+
+/// All solutions, of every year. Empty without the `registry` feature.
+#[cfg(all(feature = "registry", not(test)))]
+pub use solutions::SOLUTIONS;
+
+#[cfg(not(all(feature = "registry", not(test))))]
+pub static SOLUTIONS: &[Solution] = &[];
+
+/// Returns the solution for a puzzle, if it has been scaffolded.
+pub fn find(puzzle: Puzzle) -> Option<&'static Solution> {
+    SOLUTIONS.iter().find(|solution| solution.puzzle == puzzle)
+}
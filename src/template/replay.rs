@@ -0,0 +1,239 @@
+//! Recorded frames of a solution, written with `--record` and played back by `cargo replay`.
+//!
+//! A replay is a text file of frames, each with a header line followed by its picture:
+//!
+//! ```text
+//! === part 1, frame 1: guard has hit an obstacle
+//! ....#.....
+//! ....^....#
+//! ```
+use std::{fmt::Display, path::PathBuf};
+
+use crate::template::Puzzle;
+
+const HEADER: &str = "=== part ";
+
+/// A frame of a replay, see [`crate::template::visualize::frame`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ReplayFrame {
+    pub part: u8,
+    pub number: usize,
+    pub title: String,
+    /// The picture, which may contain ANSI colours.
+    pub picture: String,
+}
+
+impl Display for ReplayFrame {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(
+            f,
+            "{HEADER}{}, frame {}: {}",
+            self.part, self.number, self.title
+        )?;
+        writeln!(f, "{}", self.picture)
+    }
+}
+
+/// `data/replays/NN.txt` for the home year, `data/<year>/replays/NN.txt` for any other year.
+pub fn replay_path(puzzle: Puzzle) -> PathBuf {
+    puzzle.data_file("data", "replays", "txt")
+}
+
+/// Read the frames of a replay file. Text before the first header is ignored.
+pub fn parse_replay(text: &str) -> Result<Vec<ReplayFrame>, String> {
+    let mut frames: Vec<ReplayFrame> = vec![];
+
+    for (index, line) in text.lines().enumerate() {
+        if let Some(header) = line.strip_prefix(HEADER) {
+            frames.push(
+                parse_header(header).ok_or(format!("line {}: invalid frame header", index + 1))?,
+            );
+        } else if let Some(frame) = frames.last_mut() {
+            if !frame.picture.is_empty() {
+                frame.picture.push('\n');
+            }
+            frame.picture.push_str(line);
+        }
+    }
+
+    Ok(frames)
+}
+
+/// `1, frame 3: title`
+fn parse_header(header: &str) -> Option<ReplayFrame> {
+    let (part, rest) = header.split_once(", frame ")?;
+    let (number, title) = rest.split_once(": ")?;
+
+    Some(ReplayFrame {
+        part: part.parse().ok()?,
+        number: number.parse().ok()?,
+        title: title.into(),
+        picture: String::new(),
+    })
+}
+
+/// What a key press asks the [`Player`] to do.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Control {
+    TogglePlay,
+    Next,
+    Previous,
+    First,
+    Last,
+    Faster,
+    Slower,
+    Quit,
+}
+
+impl Control {
+    /// The control for a key: space, `n`/`p` or the arrow keys, `g`/`G`, `+`/`-` and `q`.
+    pub fn from_key(key: &[u8]) -> Option<Self> {
+        match key {
+            b" " => Some(Self::TogglePlay),
+            b"n" | b"l" | b"\x1b[C" => Some(Self::Next),
+            b"p" | b"h" | b"\x1b[D" => Some(Self::Previous),
+            b"g" | b"\x1b[H" => Some(Self::First),
+            b"G" | b"\x1b[F" => Some(Self::Last),
+            b"+" | b"=" | b"\x1b[A" => Some(Self::Faster),
+            b"-" | b"\x1b[B" => Some(Self::Slower),
+            // Ctrl-C arrives as a key while the terminal is in raw mode.
+            b"q" | b"\x03" => Some(Self::Quit),
+            _ => None,
+        }
+    }
+}
+
+pub const MAX_FPS: u32 = 240;
+
+/// The position and speed of a replay being played back.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Player {
+    pub index: usize,
+    pub playing: bool,
+    pub fps: u32,
+    frames: usize,
+}
+
+impl Player {
+    pub fn new(frames: usize, fps: u32) -> Self {
+        Self {
+            index: 0,
+            playing: frames > 1,
+            fps: fps.clamp(1, MAX_FPS),
+            frames,
+        }
+    }
+
+    /// Apply a control, returning whether to keep playing back. Stepping pauses the replay.
+    pub fn control(&mut self, control: Control) -> bool {
+        let last = self.frames.saturating_sub(1);
+
+        match control {
+            Control::TogglePlay => {
+                // playing from the last frame starts over.
+                if !self.playing && self.index == last {
+                    self.index = 0;
+                }
+                self.playing = !self.playing && self.frames > 1;
+            }
+            Control::Next => {
+                self.playing = false;
+                self.index = (self.index + 1).min(last);
+            }
+            Control::Previous => {
+                self.playing = false;
+                self.index = self.index.saturating_sub(1);
+            }
+            Control::First => self.index = 0,
+            Control::Last => {
+                self.playing = false;
+                self.index = last;
+            }
+            Control::Faster => self.fps = (self.fps * 2).min(MAX_FPS),
+            Control::Slower => self.fps = (self.fps / 2).max(1),
+            Control::Quit => return false,
+        }
+
+        true
+    }
+
+    /// Move on to the next frame while playing, pausing at the last one.
+    pub fn tick(&mut self) {
+        if !self.playing {
+            return;
+        }
+
+        if self.index + 1 < self.frames {
+            self.index += 1;
+        }
+        if self.index + 1 >= self.frames {
+            self.playing = false;
+        }
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{parse_replay, Control, Player, ReplayFrame};
+
+    fn frame(part: u8, number: usize, picture: &str) -> ReplayFrame {
+        ReplayFrame {
+            part,
+            number,
+            title: format!("step {number}"),
+            picture: picture.into(),
+        }
+    }
+
+    #[test]
+    fn reads_written_frames() {
+        let frames = vec![
+            frame(1, 1, "#.\n.\x1b[31m^\x1b[0m"),
+            frame(1, 2, "#.\n.."),
+            frame(2, 1, "0..1"),
+        ];
+        let text: String = frames.iter().map(ReplayFrame::to_string).collect();
+
+        assert_eq!(parse_replay(&text), Ok(frames));
+        assert_eq!(parse_replay(""), Ok(vec![]));
+        assert!(parse_replay("=== part x, frame 1: oops\n").is_err());
+    }
+
+    #[test]
+    fn plays_until_the_last_frame() {
+        let mut player = Player::new(3, 10);
+        assert!(player.playing);
+
+        player.tick();
+        player.tick();
+        assert_eq!((player.index, player.playing), (2, false));
+
+        // playing again starts over.
+        assert!(player.control(Control::TogglePlay));
+        assert_eq!((player.index, player.playing), (0, true));
+    }
+
+    #[test]
+    fn steps_and_changes_speed() {
+        let mut player = Player::new(3, 10);
+
+        player.control(Control::Previous);
+        assert_eq!((player.index, player.playing), (0, false));
+        player.control(Control::Next);
+        player.control(Control::Next);
+        player.control(Control::Next);
+        assert_eq!(player.index, 2);
+
+        player.control(Control::Faster);
+        assert_eq!(player.fps, 20);
+        (0..10).for_each(|_| {
+            player.control(Control::Slower);
+        });
+        assert_eq!(player.fps, 1);
+
+        assert_eq!(Control::from_key(b"\x1b[C"), Some(Control::Next));
+        assert!(!player.control(Control::Quit));
+    }
+}
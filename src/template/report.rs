@@ -0,0 +1,192 @@
+use std::{collections::HashMap, str::FromStr, time::Duration};
+use tinyjson::JsonValue;
+
+use crate::template::{bench::BenchStats, Day, Puzzle, Year};
+
+/// The outcome of running a single part of a solution.
+/// Solution binaries print one of these per line when called with `--format json`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct PartReport {
+    pub puzzle: Puzzle,
+    pub part: u8,
+    pub answer: Option<String>,
+    /// Why the part failed, if it returned an error.
+    pub error: Option<String>,
+    /// The median sample when benched, otherwise the time of the only run.
+    pub duration: Duration,
+    pub samples: u128,
+    /// Only present when the part was benched with `--time`.
+    pub stats: Option<BenchStats>,
+}
+
+impl PartReport {
+    pub fn is_success(&self) -> bool {
+        self.answer.is_some()
+    }
+
+    /// Serialize the report to a single line of JSON.
+    pub fn to_json_line(&self) -> String {
+        // stringify never fails for values built from `From<&PartReport>`.
+        JsonValue::from(self).stringify().unwrap()
+    }
+
+    /// Parse a line printed by a solution binary. Returns `None` for lines that are not reports,
+    /// e.g. debug output printed by the solution itself.
+    pub fn from_json_line(line: &str) -> Option<Self> {
+        let json = JsonValue::from_str(line.trim()).ok()?;
+        PartReport::try_from(&json).ok()
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<&PartReport> for JsonValue {
+    fn from(value: &PartReport) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert(
+            "year".into(),
+            JsonValue::Number(f64::from(value.puzzle.year.into_inner())),
+        );
+        map.insert(
+            "day".into(),
+            JsonValue::Number(f64::from(value.puzzle.day.into_inner())),
+        );
+        map.insert("part".into(), JsonValue::Number(f64::from(value.part)));
+        map.insert(
+            "answer".into(),
+            match &value.answer {
+                Some(answer) => JsonValue::String(answer.clone()),
+                None => JsonValue::Null,
+            },
+        );
+        map.insert(
+            "error".into(),
+            match &value.error {
+                Some(error) => JsonValue::String(error.clone()),
+                None => JsonValue::Null,
+            },
+        );
+        map.insert("success".into(), JsonValue::Boolean(value.is_success()));
+        #[allow(clippy::cast_precision_loss)]
+        map.insert(
+            "duration_nanos".into(),
+            JsonValue::Number(value.duration.as_nanos() as f64),
+        );
+        #[allow(clippy::cast_precision_loss)]
+        map.insert("samples".into(), JsonValue::Number(value.samples as f64));
+        map.insert(
+            "stats".into(),
+            match &value.stats {
+                Some(stats) => JsonValue::from(stats),
+                None => JsonValue::Null,
+            },
+        );
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for PartReport {
+    type Error = String;
+
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected report to be a JSON object.")?;
+
+        let number = |key: &str| {
+            json.get(key)
+                .and_then(|v| v.get::<f64>().copied())
+                .filter(|n| *n >= 0_f64)
+                .ok_or(format!("Expected report.{key} to be a positive number."))
+        };
+
+        let year = Year::new(number("year")? as u16).ok_or("Expected report.year to be a Year.")?;
+
+        let day = Day::new(number("day")? as u8).ok_or("Expected report.day to be a Day.")?;
+
+        let part = number("part")? as u8;
+
+        let answer = json
+            .get("answer")
+            .map(|v| if v.is_null() { None } else { v.get::<String>() })
+            .ok_or("Expected report.answer to be null or string.")?;
+
+        // reports printed before errors were reported have no error.
+        let error = json.get("error").and_then(|v| v.get::<String>()).cloned();
+
+        let duration = Duration::from_nanos(number("duration_nanos")? as u64);
+
+        let samples = number("samples")? as u128;
+
+        let stats = match json.get("stats") {
+            Some(v) if !v.is_null() => Some(BenchStats::try_from(v)?),
+            _ => None,
+        };
+
+        Ok(PartReport {
+            puzzle: Puzzle::new(year, day),
+            part,
+            answer: answer.cloned(),
+            error,
+            duration,
+            samples,
+            stats,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::time::Duration;
+
+    use super::PartReport;
+    use crate::{
+        day,
+        template::{bench::BenchStats, Puzzle},
+        year,
+    };
+
+    #[test]
+    fn round_trips_reports() {
+        let report = PartReport {
+            puzzle: Puzzle::new(year!(2023), day!(3)),
+            part: 2,
+            answer: Some("Part 1: (74.13ns @ 5 samples)\nsecond line".into()),
+            error: None,
+            duration: Duration::from_nanos(74_130),
+            samples: 5,
+            stats: BenchStats::from_samples(&[Duration::from_nanos(74_130); 5]),
+        };
+        let line = report.to_json_line();
+        assert!(!line.contains('\n'));
+        assert_eq!(PartReport::from_json_line(&line), Some(report));
+    }
+
+    #[test]
+    fn round_trips_failed_parts() {
+        let report = PartReport {
+            puzzle: day!(25).into(),
+            part: 1,
+            answer: None,
+            error: Some("line 3, column 5: invalid digit".into()),
+            duration: Duration::from_millis(2),
+            samples: 1,
+            stats: None,
+        };
+        let line = report.to_json_line();
+        assert!(line.contains(r#""success":false"#));
+        assert_eq!(PartReport::from_json_line(&line), Some(report));
+    }
+
+    #[test]
+    fn ignores_other_lines() {
+        assert_eq!(PartReport::from_json_line("Part 1: 0 (74.13ns)"), None);
+        assert_eq!(PartReport::from_json_line("[1, 2, 3]"), None);
+        assert_eq!(PartReport::from_json_line(r#"{ "day": 26 }"#), None);
+    }
+}
@@ -1,40 +1,92 @@
-use std::{collections::HashSet, io};
+use std::{
+    collections::{BTreeMap, HashSet},
+    num::NonZeroUsize,
+    panic,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        mpsc,
+    },
+    thread,
+    time::Duration,
+};
 
-use crate::template::{Day, ANSI_BOLD, ANSI_ITALIC, ANSI_RESET};
+use crate::template::{Day, Puzzle, Year, ANSI_BOLD, ANSI_ITALIC, ANSI_RESET};
 
 use super::{
     all_days,
+    bench::BenchConfig,
+    inputs::Input,
+    registry,
+    report::PartReport,
+    runner::print_report,
     timings::{Timing, Timings},
 };
 
-pub fn run_multi(days_to_run: &HashSet<Day>, is_release: bool, is_timed: bool) -> Option<Timings> {
+/// Run the solutions for a set of days of a year. Passing a [`BenchConfig`] benches them and returns their timings.
+///
+/// With `jobs` greater than one, days run concurrently on that many threads, `0` uses one thread per core.
+/// Each day's output is then printed in one piece once it has finished, still in day order.
+pub fn run_multi(
+    year: Year,
+    days_to_run: &HashSet<Day>,
+    bench: Option<BenchConfig>,
+    jobs: usize,
+) -> Option<Timings> {
+    let jobs = if jobs == 0 {
+        thread::available_parallelism().map_or(1, NonZeroUsize::get)
+    } else {
+        jobs
+    };
+
     let mut timings: Vec<Timing> = Vec::with_capacity(days_to_run.len());
 
     let mut need_space = false;
+    let mut print_header = |day: Day| {
+        if need_space {
+            println!();
+        }
+        need_space = true;
 
-    // NOTE: use non-duplicate, sorted day values.
-    all_days()
-        .filter(|day| days_to_run.contains(day))
-        .for_each(|day| {
-            if need_space {
-                println!();
-            }
-            need_space = true;
+        println!("{ANSI_BOLD}Day {day}{ANSI_RESET}");
+        println!("------");
+    };
 
-            println!("{ANSI_BOLD}Day {day}{ANSI_RESET}");
-            println!("------");
+    let mut finish_day = |day: Day, result: Result<Vec<PartReport>, String>| match result {
+        Ok(reports) if !reports.is_empty() => {
+            let val = timing_from_reports(&reports, day);
+            timings.push(val);
+        }
+        Ok(_) => println!("Not solved."),
+        Err(e) => {
+            eprintln!("{e}");
+            println!("Not solved.");
+        }
+    };
 
-            let output = child_commands::run_solution(day, is_timed, is_release).unwrap();
+    // NOTE: use non-duplicate, sorted day values.
+    let days: Vec<Day> = all_days().filter(|day| days_to_run.contains(day)).collect();
+
+    if jobs > 1 {
+        if bench.is_some() {
+            println!("{ANSI_BOLD}Benching {jobs} days at a time, timings may be inflated by contention.{ANSI_RESET}\n");
+        }
 
-            if output.is_empty() {
-                println!("Not solved.");
-            } else {
-                let val = child_commands::parse_exec_time(&output, day);
-                timings.push(val);
+        run_parallel(year, &days, bench, jobs, |day, result| {
+            print_header(day);
+            if let Ok(reports) = &result {
+                reports.iter().for_each(print_report);
             }
+            finish_day(day, result);
         });
+    } else {
+        for day in days {
+            print_header(day);
+            let result = run_day(Puzzle::new(year, day), &Input::Own, bench, true);
+            finish_day(day, result);
+        }
+    }
 
-    if is_timed {
+    if bench.is_some() {
         let timings = Timings { data: timings };
         let total_millis = timings.total_millis();
         println!(
@@ -46,212 +98,186 @@ pub fn run_multi(days_to_run: &HashSet<Day>, is_release: bool, is_timed: bool) -
     }
 }
 
-#[allow(dead_code)]
-#[derive(Debug)]
-pub enum Error {
-    BrokenPipe,
-    IO(io::Error),
-}
-
-impl From<std::io::Error> for Error {
-    fn from(e: std::io::Error) -> Self {
-        Error::IO(e)
-    }
-}
-
-#[must_use]
-pub fn get_path_for_bin(day: Day) -> String {
-    format!("./src/bin/{day}.rs")
-}
-
-/// All solutions live in isolated binaries.
-/// This module encapsulates interaction with these binaries, both invoking them as well as parsing the timing output.
-pub mod child_commands {
-    use super::{get_path_for_bin, Error};
-    use crate::template::Day;
-    use std::{
-        io::{BufRead, BufReader},
-        path::Path,
-        process::{Command, Stdio},
-        thread,
-    };
-
-    /// Run the solution bin for a given day
-    pub fn run_solution(day: Day, is_timed: bool, is_release: bool) -> Result<Vec<String>, Error> {
-        // skip command invocation for days that have not been scaffolded yet.
-        if !Path::new(&get_path_for_bin(day)).exists() {
-            return Ok(vec![]);
+/// Run days on `jobs` worker threads, calling `on_day` for each one in day order.
+/// A day is handed to `on_day` as soon as it and all days before it have finished.
+fn run_parallel(
+    year: Year,
+    days: &[Day],
+    bench: Option<BenchConfig>,
+    jobs: usize,
+    mut on_day: impl FnMut(Day, Result<Vec<PartReport>, String>),
+) {
+    let next_day = AtomicUsize::new(0);
+    let (sender, receiver) = mpsc::channel();
+
+    thread::scope(|scope| {
+        for _ in 0..jobs.min(days.len()) {
+            let sender = sender.clone();
+            let next_day = &next_day;
+            scope.spawn(move || {
+                while let Some(&day) = days.get(next_day.fetch_add(1, Ordering::Relaxed)) {
+                    let result = run_day(Puzzle::new(year, day), &Input::Own, bench, false);
+                    if sender.send((day, result)).is_err() {
+                        break;
+                    }
+                }
+            });
         }
+        drop(sender);
 
-        let day_padded = day.to_string();
-        let mut args = vec!["run", "--quiet", "--bin", &day_padded];
+        // days can finish out of order, so hold on to them until it is their turn.
+        let mut finished = BTreeMap::new();
+        let mut remaining = days.iter().peekable();
 
-        if is_release {
-            args.push("--release");
-        }
-
-        if is_timed {
-            // mirror `--time` flag to child invocations.
-            args.push("--");
-            args.push("--time");
+        for (day, result) in receiver {
+            finished.insert(day, result);
+            while let Some(result) = remaining.peek().and_then(|day| finished.remove(*day)) {
+                on_day(*remaining.next().unwrap(), result);
+            }
         }
+    });
+}
 
-        // spawn child command with piped stdout/stderr.
-        // forward output to stdout/stderr while grabbing stdout lines.
+/// Run the solution for a puzzle on an input in this process.
+/// With `live`, each part is printed as it runs, otherwise nothing is printed.
+/// Returns no reports for days that have not been scaffolded yet, and an error if the input is missing.
+/// A part that panics is reported as failed, so it does not take down the other days.
+pub fn run_day(
+    puzzle: Puzzle,
+    input: &Input,
+    bench: Option<BenchConfig>,
+    live: bool,
+) -> Result<Vec<PartReport>, String> {
+    let Some(solution) = registry::find(puzzle) else {
+        return Ok(vec![]);
+    };
 
-        let mut cmd = Command::new("cargo")
-            .args(&args)
-            .stdout(Stdio::piped())
-            .stderr(Stdio::piped())
-            .spawn()?;
+    let input = input.read(puzzle)?;
+
+    let reports = solution
+        .parts
+        .iter()
+        .map(|part| {
+            let failed = PartReport {
+                puzzle,
+                part: part.part,
+                answer: None,
+                error: Some("the solution panicked".into()),
+                duration: Duration::ZERO,
+                samples: 1,
+                stats: None,
+            };
+            let report =
+                panic::catch_unwind(|| (part.run)(&input, &[], bench, live)).unwrap_or(failed);
+            if live {
+                print_report(&report);
+            }
+            report
+        })
+        .collect();
 
-        let stdout = BufReader::new(cmd.stdout.take().ok_or(super::Error::BrokenPipe)?);
-        let stderr = BufReader::new(cmd.stderr.take().ok_or(super::Error::BrokenPipe)?);
+    Ok(reports)
+}
 
-        let mut output = vec![];
+pub fn timing_from_reports(reports: &[PartReport], day: Day) -> Timing {
+    let mut timings = Timing {
+        day,
+        part_1: None,
+        part_2: None,
+        total_nanos: 0_f64,
+        part_1_stats: None,
+        part_2_stats: None,
+    };
 
-        let thread = thread::spawn(move || {
-            stderr.lines().for_each(|line| {
-                eprintln!("{}", line.unwrap());
-            });
-        });
+    // parts without an answer did not produce a meaningful timing.
+    for report in reports.iter().filter(|r| r.is_success()) {
+        let timing_str = format!("{:.1?}", report.duration);
 
-        for line in stdout.lines() {
-            let line = line.unwrap();
-            println!("{line}");
-            output.push(line);
+        match report.part {
+            1 => {
+                timings.part_1 = Some(timing_str);
+                timings.part_1_stats.clone_from(&report.stats);
+            }
+            2 => {
+                timings.part_2 = Some(timing_str);
+                timings.part_2_stats.clone_from(&report.stats);
+            }
+            _ => continue,
         }
 
-        thread.join().unwrap();
-        cmd.wait()?;
-
-        Ok(output)
+        #[allow(clippy::cast_precision_loss)]
+        let nanos = report.duration.as_nanos() as f64;
+        timings.total_nanos += nanos;
     }
 
-    pub fn parse_exec_time(output: &[String], day: Day) -> super::Timing {
-        let mut timings = super::Timing {
-            day,
-            part_1: None,
-            part_2: None,
-            total_nanos: 0_f64,
-        };
-
-        output
-            .iter()
-            .filter_map(|l| {
-                if !l.contains(" samples)") {
-                    return None;
-                }
+    timings
+}
 
-                let Some((timing_str, nanos)) = parse_time(l) else {
-                    eprintln!("Could not parse timings from line: {l}");
-                    return None;
-                };
-
-                let part = l.split(':').next()?;
-                Some((part, timing_str, nanos))
-            })
-            .for_each(|(part, timing_str, nanos)| {
-                if part.contains("Part 1") {
-                    timings.part_1 = Some(timing_str.into());
-                } else if part.contains("Part 2") {
-                    timings.part_2 = Some(timing_str.into());
-                }
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::time::Duration;
 
-                timings.total_nanos += nanos;
-            });
+    use super::{run_parallel, timing_from_reports};
 
-        timings
-    }
+    use crate::{
+        day,
+        template::{all_days, report::PartReport, Day, Year},
+    };
 
-    fn parse_to_float(s: &str, postfix: &str) -> Option<f64> {
-        s.split(postfix).next()?.parse().ok()
+    fn report(part: u8, answer: Option<&str>, nanos: u64) -> PartReport {
+        PartReport {
+            puzzle: day!(1).into(),
+            part,
+            answer: answer.map(Into::into),
+            error: None,
+            duration: Duration::from_nanos(nanos),
+            samples: 100,
+            stats: None,
+        }
     }
 
-    fn parse_time(line: &str) -> Option<(&str, f64)> {
-        // for possible time formats, see: https://github.com/rust-lang/rust/blob/1.64.0/library/core/src/time.rs#L1176-L1200
-        let str_timing = line
-            .split(" samples)")
-            .next()?
-            .split('(')
-            .last()?
-            .split('@')
-            .next()?
-            .trim();
-
-        let parsed_timing = match str_timing {
-            s if s.contains("ns") => s.split("ns").next()?.parse::<f64>().ok(),
-            s if s.contains("µs") => parse_to_float(s, "µs").map(|x| x * 1000_f64),
-            s if s.contains("ms") => parse_to_float(s, "ms").map(|x| x * 1_000_000_f64),
-            s => parse_to_float(s, "s").map(|x| x * 1_000_000_000_f64),
-        }?;
-
-        Some((str_timing, parsed_timing))
+    #[test]
+    fn collects_execution_times() {
+        let res = timing_from_reports(
+            &[report(1, Some("0"), 74), report(2, Some("10"), 74_130_000)],
+            day!(1),
+        );
+        assert_eq!(res.total_nanos, 74130074_f64);
+        assert_eq!(res.part_1.unwrap(), "74.0ns");
+        assert_eq!(res.part_2.unwrap(), "74.1ms");
     }
 
-    /// copied from: https://github.com/rust-lang/rust/blob/1.64.0/library/std/src/macros.rs#L328-L333
-    #[cfg(feature = "test_lib")]
-    macro_rules! assert_approx_eq {
-        ($a:expr, $b:expr) => {{
-            let (a, b) = (&$a, &$b);
-            assert!(
-                (*a - *b).abs() < 1.0e-6,
-                "{} is not approximately equal to {}",
-                *a,
-                *b
-            );
-        }};
+    #[test]
+    fn collects_with_patterns_in_answers() {
+        let res = timing_from_reports(
+            &[
+                report(1, Some("@ @ @ ( ) ms (2s @ 5 samples)"), 2_000_000_000),
+                report(2, Some("10s"), 100_000_000),
+            ],
+            day!(1),
+        );
+        assert_eq!(res.total_nanos, 2100000000_f64);
+        assert_eq!(res.part_1.unwrap(), "2.0s");
+        assert_eq!(res.part_2.unwrap(), "100.0ms");
     }
 
-    #[cfg(feature = "test_lib")]
-    mod tests {
-        use super::parse_exec_time;
-
-        use crate::day;
-
-        #[test]
-        fn parses_execution_times() {
-            let res = parse_exec_time(
-                &[
-                    "Part 1: 0 (74.13ns @ 100000 samples)".into(),
-                    "Part 2: 10 (74.13ms @ 99999 samples)".into(),
-                    "".into(),
-                ],
-                day!(1),
-            );
-            assert_approx_eq!(res.total_nanos, 74130074.13_f64);
-            assert_eq!(res.part_1.unwrap(), "74.13ns");
-            assert_eq!(res.part_2.unwrap(), "74.13ms");
-        }
-
-        #[test]
-        fn parses_with_patterns_in_input() {
-            let res = parse_exec_time(
-                &[
-                    "Part 1: @ @ @ ( ) ms (2s @ 5 samples)".into(),
-                    "Part 2: 10s (100ms @ 1 samples)".into(),
-                    "".into(),
-                ],
-                day!(1),
-            );
-            assert_approx_eq!(res.total_nanos, 2100000000_f64);
-            assert_eq!(res.part_1.unwrap(), "2s");
-            assert_eq!(res.part_2.unwrap(), "100ms");
-        }
+    #[test]
+    fn skips_missing_parts() {
+        let res = timing_from_reports(&[report(1, None, 50), report(2, None, 50)], day!(1));
+        assert_eq!(res.total_nanos, 0_f64);
+        assert_eq!(res.part_1.is_none(), true);
+        assert_eq!(res.part_2.is_none(), true);
+    }
 
-        #[test]
-        fn parses_missing_parts() {
-            let res = parse_exec_time(
-                &[
-                    "Part 1: ✖        ".into(),
-                    "Part 2: ✖        ".into(),
-                    "".into(),
-                ],
-                day!(1),
-            );
-            assert_approx_eq!(res.total_nanos, 0_f64);
-            assert_eq!(res.part_1.is_none(), true);
-            assert_eq!(res.part_2.is_none(), true);
-        }
+    #[test]
+    fn runs_parallel_days_in_order() {
+        let days: Vec<Day> = all_days().collect();
+        let mut seen = vec![];
+        // no solutions are registered in the library's tests, so every day finishes instantly and out of order.
+        run_parallel(Year::home(), &days, None, 4, |day, result| {
+            assert_eq!(result, Ok(vec![]));
+            seen.push(day);
+        });
+        assert_eq!(seen, days);
     }
 }
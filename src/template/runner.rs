@@ -1,35 +1,231 @@
 /// Encapsulates code that interacts with solution functions.
 use std::fmt::Display;
 use std::hint::black_box;
-use std::io::{stdout, Write};
-use std::process::Output;
+use std::io::{self, stderr, stdout, Write};
 use std::time::{Duration, Instant};
-use std::{cmp, env, process};
+use std::{env, fs, process};
 
+use crate::template::answers::Answers;
+use crate::template::aoc_client::{self, AocClientError, SubmitOutcome};
+use crate::template::bench::{BenchConfig, BenchStats, MIN_SAMPLES};
+use crate::template::examples::{ExampleCase, Examples};
+use crate::template::inputs::Input;
+use crate::template::params::{Param, Params};
+use crate::template::registry::Solution;
+use crate::template::report::PartReport;
+use crate::template::submissions::Submissions;
+use crate::template::visualize;
 use crate::template::ANSI_BOLD;
-use crate::template::{aoc_cli, Day, ANSI_ITALIC, ANSI_RESET};
+use crate::template::{Puzzle, ANSI_ITALIC, ANSI_RESET};
 
-pub fn run_part<I: Copy, T: Display>(func: impl Fn(I) -> Option<T>, input: I, day: Day, part: u8) {
-    let part_str = format!("Part {part}");
+/// How a solution binary reports its results, selected with `--format <text|json>`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum OutputFormat {
+    /// Human readable output, printed while the solution runs.
+    Text,
+    /// One [`PartReport`] per line, as JSON.
+    Json,
+}
 
-    let (result, duration, samples) =
-        run_timed(func, input, |result| print_result(result, &part_str, ""));
+impl OutputFormat {
+    /// Parse the `--format` argument passed to the binary, defaulting to [`OutputFormat::Text`].
+    fn from_args() -> Self {
+        let args: Vec<String> = env::args().collect();
 
-    print_result(&result, &part_str, &format_duration(&duration, samples));
+        let Some(format_index) = args.iter().position(|x| x == "--format") else {
+            return Self::Text;
+        };
 
-    if let Some(result) = result {
-        submit_result(result, day, part);
+        match args.get(format_index + 1).map(String::as_str) {
+            Some("text") => Self::Text,
+            Some("json") => Self::Json,
+            _ => {
+                eprintln!("Unexpected command-line input. Format: --format <text|json>");
+                process::exit(1);
+            }
+        }
+    }
+
+    /// Where messages other than the results go: stdout for text, stderr for JSON,
+    /// so that stdout stays a stream of JSON lines.
+    fn messages(self) -> Box<dyn Write> {
+        match self {
+            Self::Text => Box::new(stdout()),
+            Self::Json => Box::new(stderr()),
+        }
     }
 }
 
-/// Run a solution part. The behavior differs depending on whether we are running a release or debug build:
-///  1. in debug, the function is executed once.
-///  2. in release, the function is benched (approx. 1 second of execution time or 10 samples, whatever take longer.)
-fn run_timed<I: Copy, T>(
-    func: impl Fn(I) -> T,
+/// The entry point of a solution binary: runs every part of the solution on the input,
+/// printing the results in the `--format` requested and submitting them if `--submit` is passed.
+/// Only answers for your own input can be submitted. With `--examples`, the examples are run first.
+/// Parameters can be overridden with `--param key=value`, for the input only.
+pub fn run_solution(solution: &Solution, input: &Input) {
+    let params = match Param::from_args() {
+        Ok(params) => params,
+        Err(e) => {
+            eprintln!("{e}");
+            process::exit(1);
+        }
+    };
+
+    if *input != Input::Own && env::args().any(|x| x == "--submit") {
+        eprintln!("Only answers for your own input can be submitted, not for the {input}.");
+        process::exit(1);
+    }
+
+    let format = OutputFormat::from_args();
+
+    if env::args().any(|x| x == "--examples") {
+        let mut out = format.messages();
+        let printed = run_examples(solution, &mut out)
+            .and_then(|()| writeln!(out, "{ANSI_BOLD}Input{ANSI_RESET}\n------"));
+        if let Err(e) = printed {
+            eprintln!("Could not print the examples: {e}");
+        }
+    }
+
+    let input = match input.read(solution.puzzle) {
+        Ok(input) => input,
+        Err(e) => {
+            eprintln!("{e}");
+            process::exit(1);
+        }
+    };
+
+    let bench = env::args()
+        .any(|x| x == "--time")
+        .then(BenchConfig::from_args);
+
+    let mut failed = false;
+
+    for part in solution.parts {
+        visualize::start_part(part.part);
+        let report = (part.run)(&input, &params, bench, format == OutputFormat::Text);
+        failed |= report.error.is_some();
+
+        match format {
+            OutputFormat::Text => print_report(&report),
+            OutputFormat::Json => println!("{}", report.to_json_line()),
+        }
+
+        if let Some(answer) = &report.answer
+            && let Some(Err(e)) = submit_result(answer, solution.puzzle, part.part, format)
+        {
+            eprintln!("failed to submit: {e}");
+            process::exit(1);
+        }
+    }
+
+    if failed {
+        process::exit(1);
+    }
+}
+
+/// Run the cases in the day's examples manifest and print each answer next to the expected one to `out`.
+/// Without a manifest, every part is run on the day's example file.
+fn run_examples(solution: &Solution, out: &mut impl Write) -> io::Result<()> {
+    let puzzle = solution.puzzle;
+    let examples = match Examples::read(puzzle) {
+        Ok(Some(examples)) => examples,
+        Ok(None) => Examples {
+            cases: vec![ExampleCase {
+                file: format!("{}.txt", puzzle.day),
+                part_1: None,
+                part_2: None,
+                params: vec![],
+            }],
+        },
+        Err(e) => {
+            eprintln!("{e}\n");
+            return Ok(());
+        }
+    };
+
+    let examples_dir = puzzle.year.data_dir("data").join("examples");
+
+    for case in &examples.cases {
+        writeln!(out, "{ANSI_BOLD}Example {}{ANSI_RESET}", case.name())?;
+        writeln!(out, "------")?;
+
+        let path = examples_dir.join(&case.file);
+        let Ok(input) = fs::read_to_string(&path) else {
+            writeln!(out, "Could not read example file \"{}\".\n", path.display())?;
+            continue;
+        };
+
+        for part in solution.parts {
+            visualize::start_part(part.part);
+            let PartReport { answer, error, .. } = (part.run)(&input, &case.params, None, false);
+            let expected = case.expected(part.part);
+
+            let verdict = match (expected, &answer) {
+                (None, _) => String::new(),
+                (Some(expected), Some(answer)) if expected == answer => " ✔".into(),
+                (Some(expected), _) if expected.contains('\n') => {
+                    format!(" ✖ expected:\n{expected}")
+                }
+                (Some(expected), _) => format!(" ✖ expected {ANSI_BOLD}{expected}{ANSI_RESET}"),
+            };
+
+            match answer {
+                Some(answer) if answer.contains('\n') => {
+                    writeln!(out, "Part {}:{verdict}\n{answer}", part.part)?;
+                }
+                Some(answer) => {
+                    writeln!(
+                        out,
+                        "Part {}: {ANSI_BOLD}{answer}{ANSI_RESET}{verdict}",
+                        part.part
+                    )?;
+                }
+                None => writeln!(out, "Part {}: ✖{verdict}", part.part)?,
+            }
+
+            if let Some(error) = error {
+                writeln!(out, "  {ANSI_ITALIC}{error}{ANSI_RESET}")?;
+            }
+        }
+
+        writeln!(out)?;
+    }
+
+    Ok(())
+}
+
+/// What a solution part can return: `Option<T>`, or `Result<T, E>` to explain why there is no answer.
+pub trait PartResult {
+    /// The answer, `Ok(None)` for a part that gave none, or the error it failed with.
+    fn into_answer(self) -> Result<Option<String>, String>;
+}
+
+impl<T: Display> PartResult for Option<T> {
+    fn into_answer(self) -> Result<Option<String>, String> {
+        Ok(self.map(|answer| answer.to_string()))
+    }
+}
+
+impl<T: Display, E: Display> PartResult for Result<T, E> {
+    fn into_answer(self) -> Result<Option<String>, String> {
+        self.map(|answer| Some(answer.to_string()))
+            .map_err(|e| e.to_string())
+    }
+}
+
+/// Run a solution part and report on it. The behavior differs depending on whether a [`BenchConfig`] is passed:
+///  1. without it, the function is executed once.
+///  2. with it, the function is benched, see [`bench`]. The reported duration is the median sample.
+///
+/// With `show_progress`, the answer is printed as soon as it is known, followed by a note while benching.
+/// A part that fails with an error is not benched.
+pub fn solve_part<I: Copy, R: PartResult>(
+    func: impl Fn(I) -> R,
     input: I,
-    hook: impl Fn(&T),
-) -> (T, Duration, u128) {
+    puzzle: Puzzle,
+    part: u8,
+    bench_config: Option<BenchConfig>,
+    show_progress: bool,
+) -> PartReport {
     let timer = Instant::now();
     let result = {
         #[cfg(feature = "dhat-heap")]
@@ -38,48 +234,125 @@ fn run_timed<I: Copy, T>(
         func(input)
     };
     let base_time = timer.elapsed();
+    let (answer, error) = match result.into_answer() {
+        Ok(answer) => (answer, None),
+        Err(e) => (None, Some(e)),
+    };
 
-    hook(&result);
+    if show_progress {
+        print_result(&answer, &format!("Part {part}"), "");
+    }
 
-    let run = if std::env::args().any(|x| x == "--time") {
-        bench(func, input, &base_time)
-    } else {
-        (base_time, 1)
+    let (duration, stats) = match bench_config {
+        Some(config) if error.is_none() => {
+            let stats = visualize::muted(|| bench(func, input, config, show_progress));
+            (stats.median, Some(stats))
+        }
+        _ => (base_time, None),
     };
 
-    (result, run.0, run.1)
+    PartReport {
+        puzzle,
+        part,
+        answer,
+        error,
+        duration,
+        samples: stats.as_ref().map_or(1, |stats| stats.samples),
+        stats,
+    }
+}
+
+/// Run a solution part that takes parameters: the defaults of `P` with `overrides` applied, see [`Params`].
+/// Overrides that do not fit `P` are reported as the part's error.
+pub fn solve_part_with_params<P: Params, R: PartResult>(
+    func: impl Fn(&str, &P) -> R,
+    input: &str,
+    overrides: &[Param],
+    puzzle: Puzzle,
+    part: u8,
+    bench_config: Option<BenchConfig>,
+    show_progress: bool,
+) -> PartReport {
+    match P::with_overrides(overrides) {
+        Ok(params) => solve_part(
+            |input| func(input, &params),
+            input,
+            puzzle,
+            part,
+            bench_config,
+            show_progress,
+        ),
+        Err(error) => PartReport {
+            puzzle,
+            part,
+            answer: None,
+            error: Some(error),
+            duration: Duration::ZERO,
+            samples: 1,
+            stats: None,
+        },
+    }
 }
 
-fn bench<I: Copy, T>(func: impl Fn(I) -> T, input: I, base_time: &Duration) -> (Duration, u128) {
-    let mut stdout = stdout();
+/// Print a report the same way a solution binary does in text mode.
+pub fn print_report(report: &PartReport) {
+    print_result(
+        &report.answer,
+        &format!("Part {}", report.part),
+        &format_duration(&report.duration, report.samples),
+    );
+
+    if let Some(stats) = &report.stats {
+        println!("{}", format_stats(stats));
+    }
+
+    if let Some(error) = &report.error {
+        eprintln!("Part {} failed: {error}", report.part);
+    }
+}
 
-    print!(" > {ANSI_ITALIC}benching{ANSI_RESET}");
-    let _ = stdout.flush();
+/// Bench a solution part: warm up for a tenth of the budget, then take samples until the budget is used up.
+/// At least [`MIN_SAMPLES`] and at most `config.max_samples` samples are taken.
+fn bench<I: Copy, T>(
+    func: impl Fn(I) -> T,
+    input: I,
+    config: BenchConfig,
+    show_progress: bool,
+) -> BenchStats {
+    if show_progress {
+        let mut stdout = stdout();
+        print!(" > {ANSI_ITALIC}benching{ANSI_RESET}");
+        let _ = stdout.flush();
+    }
 
-    let bench_iterations =
-        (Duration::from_secs(1).as_nanos() / cmp::max(base_time.as_nanos(), 10)).clamp(10, 10000);
+    let warmup = Instant::now();
+    let mut warmup_iterations = 0;
+    while warmup_iterations < 1
+        || (warmup.elapsed() < config.warmup() && warmup_iterations < config.max_samples)
+    {
+        black_box(func(black_box(input)));
+        warmup_iterations += 1;
+    }
 
     let mut timers: Vec<Duration> = vec![];
+    let sampling = Instant::now();
 
-    for _ in 0..bench_iterations {
+    while (timers.len() as u128) < MIN_SAMPLES
+        || (sampling.elapsed() < config.budget && (timers.len() as u128) < config.max_samples)
+    {
         let timer = Instant::now();
         black_box(func(black_box(input)));
         timers.push(timer.elapsed());
     }
 
-    (
-        #[allow(clippy::cast_possible_truncation)]
-        Duration::from_nanos(average_duration(&timers) as u64),
-        bench_iterations,
-    )
+    BenchStats::from_samples(&timers).expect("at least MIN_SAMPLES samples were taken")
 }
 
-fn average_duration(numbers: &[Duration]) -> u128 {
-    numbers
-        .iter()
-        .map(std::time::Duration::as_nanos)
-        .sum::<u128>()
-        / numbers.len() as u128
+fn format_stats(stats: &BenchStats) -> String {
+    format!(
+        "  {ANSI_ITALIC}min {:.1?} · median {:.1?} · p95 {:.1?} · max {:.1?} · σ {:.1?} · {} outliers{ANSI_RESET}",
+        stats.min, stats.median, stats.p95, stats.max, stats.std_dev, stats.outliers
+    )
 }
 
 fn format_duration(duration: &Duration, samples: u128) -> String {
@@ -127,12 +400,17 @@ fn print_result<T: Display>(result: &Option<T>, part: &str, duration_str: &str)
 
 /// Parse the arguments passed to `solve` and try to submit one part of the solution if:
 ///  1. we are in `--release` mode.
-///  2. aoc-cli is installed.
+///  2. the client is available, see [`aoc_client::client`].
+///  3. the answer is not ruled out by earlier submissions, see [`Submissions::check`].
+///
+/// Every reply is recorded in the year's `submissions.json`, and a correct answer is added to its `answers.json`.
+/// Messages go to stderr with `--format json`, see [`OutputFormat::messages`].
 fn submit_result<T: Display>(
     result: T,
-    day: Day,
+    puzzle: Puzzle,
     part: u8,
-) -> Option<Result<Output, aoc_cli::AocCommandError>> {
+    format: OutputFormat,
+) -> Option<Result<SubmitOutcome, AocClientError>> {
     let args: Vec<String> = env::args().collect();
 
     if !args.contains(&"--submit".into()) {
@@ -155,11 +433,52 @@ fn submit_result<T: Display>(
         return None;
     }
 
-    if aoc_cli::check().is_err() {
-        eprintln!("command \"aoc\" not found or not callable. Try running \"cargo install aoc-cli\" to install it.");
+    let answer = result.to_string();
+    let mut submissions = match Submissions::read_from_file(puzzle.year) {
+        Ok(submissions) => submissions,
+        Err(e) => {
+            eprintln!("Not submitting {answer}: {e}");
+            return None;
+        }
+    };
+
+    if let Err(refusal) = submissions.check(puzzle.day, part, &answer) {
+        eprintln!("Not submitting {answer}: {refusal}.");
+        return None;
+    }
+
+    let client = aoc_client::client();
+
+    if let Err(e) = client.check() {
+        eprintln!("{e}");
         process::exit(1);
     }
 
-    println!("Submitting result via aoc-cli...");
-    Some(aoc_cli::submit(day, part, &result.to_string()))
+    let mut out = format.messages();
+    let _ = writeln!(out, "Submitting result via {}...", client.name());
+    let outcome = client.submit(puzzle, part, &answer);
+
+    if let Ok(outcome) = &outcome {
+        submissions.record(puzzle.day, part, &answer, outcome);
+        if submissions.store_file(puzzle.year).is_err() {
+            eprintln!("Failed to store submission history.");
+        }
+
+        if *outcome == SubmitOutcome::Correct {
+            match Answers::read_from_file(puzzle.year) {
+                Ok(mut answers) => {
+                    answers.set(puzzle.day, None, part, &answer);
+                    match answers.store_file(puzzle.year) {
+                        Ok(()) => {
+                            let _ = writeln!(out, "Recorded the answer as known-good.");
+                        }
+                        Err(_) => eprintln!("Failed to store answers."),
+                    }
+                }
+                Err(e) => eprintln!("Did not record the answer: {e}"),
+            }
+        }
+    }
+
+    Some(outcome)
 }
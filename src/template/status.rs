@@ -0,0 +1,371 @@
+//! The progress on every day of a year, as shown by `cargo status`.
+
+use std::{collections::HashMap, fmt::Display, panic, time::Duration};
+use tinyjson::JsonValue;
+
+use crate::template::{
+    all_days,
+    answers::{Answers, Verdict},
+    examples::Examples,
+    inputs::Input,
+    registry,
+    run_multi::run_day,
+    submissions::{Reply, Submissions},
+    timings::Timings,
+    Day, Puzzle, Year,
+};
+
+/// What is known about a single day.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DayStatus {
+    pub day: Day,
+    pub scaffolded: bool,
+    /// Whether every example case passes, `None` if the day has no examples manifest or was not verified.
+    pub examples: Option<bool>,
+    /// Whether each part was solved, i.e. has a correct submission or a recorded answer.
+    pub solved: [bool; 2],
+    /// Whether the solution still gives the recorded answers on your own input, `None` if none are recorded
+    /// or the day was not verified.
+    pub verified: Option<bool>,
+    /// The total time of the last stored benchmark.
+    pub benchmark: Option<Duration>,
+}
+
+impl DayStatus {
+    pub fn stars(&self) -> usize {
+        self.solved.iter().filter(|solved| **solved).count()
+    }
+
+    /// A compact cell for the calendar, e.g. `01 SE12V   1.2ms`.
+    fn cell(&self) -> String {
+        let flag = |state: Option<bool>, letter: char| match state {
+            Some(true) => letter,
+            Some(false) => '✖',
+            None => '·',
+        };
+        let benchmark = self
+            .benchmark
+            .map_or_else(|| "-".into(), |duration| format!("{duration:.1?}"));
+
+        format!(
+            "{} {}{}{}{}{} {benchmark:>8}",
+            self.day,
+            flag(Some(self.scaffolded).filter(|s| *s), 'S'),
+            flag(self.examples, 'E'),
+            flag(Some(self.solved[0]).filter(|s| *s), '1'),
+            flag(Some(self.solved[1]).filter(|s| *s), '2'),
+            flag(self.verified, 'V'),
+        )
+    }
+}
+
+/// The status of all days of a year.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Status {
+    pub year: Year,
+    pub days: Vec<DayStatus>,
+}
+
+/// Whether a part was solved, judging by the submission history and the recorded answers.
+fn is_solved(answers: &Answers, submissions: &Submissions, day: Day, part: u8) -> bool {
+    answers.get(day, None, part).is_some()
+        || submissions
+            .data
+            .iter()
+            .any(|s| s.day == day && s.part == part && s.reply == Reply::Correct)
+}
+
+/// Run a solution's example cases. Missing example files and panics count as failures.
+fn check_examples(puzzle: Puzzle) -> Option<bool> {
+    let solution = registry::find(puzzle)?;
+    let examples = Examples::read(puzzle).ok().flatten()?;
+    let examples_dir = puzzle.year.data_dir("data").join("examples");
+
+    let passed = panic::catch_unwind(|| {
+        examples
+            .run(solution, &examples_dir)
+            .is_ok_and(|results| results.iter().all(|result| result.is_pass()))
+    });
+
+    Some(passed.unwrap_or(false))
+}
+
+/// Run a solution on your own input and compare it to the recorded answers.
+fn check_answers(puzzle: Puzzle, answers: &Answers) -> Option<bool> {
+    let recorded = (1..=2).any(|part| answers.get(puzzle.day, None, part).is_some());
+    if !recorded || registry::find(puzzle).is_none() {
+        return None;
+    }
+
+    let Ok(reports) = run_day(puzzle, &Input::Own, None, false) else {
+        return Some(false);
+    };
+
+    Some(reports.iter().all(|report| {
+        !matches!(
+            answers.check(puzzle.day, None, report.part, report.answer.as_deref()),
+            Verdict::Fail { .. }
+        )
+    }))
+}
+
+/// Run `f` without printing the messages of panics it catches, so failing solutions don't clutter the calendar.
+fn without_panic_messages<T>(f: impl FnOnce() -> T) -> T {
+    let hook = panic::take_hook();
+    panic::set_hook(Box::new(|_| {}));
+    let result = f();
+    panic::set_hook(hook);
+    result
+}
+
+impl Status {
+    /// Gather the status of every day. With `verify`, this also runs the examples of scaffolded days,
+    /// and their solutions on your own input if answers are recorded for them.
+    pub fn collect(year: Year, verify: bool) -> Result<Self, String> {
+        let answers = Answers::read_from_file(year)?;
+        let submissions = Submissions::read_from_file(year)?;
+        let timings = Timings::read_from_file(year);
+
+        let days = all_days()
+            .map(|day| {
+                let puzzle = Puzzle::new(year, day);
+                let (examples, verified) = if verify {
+                    without_panic_messages(|| {
+                        (check_examples(puzzle), check_answers(puzzle, &answers))
+                    })
+                } else {
+                    (None, None)
+                };
+
+                DayStatus {
+                    day,
+                    scaffolded: registry::find(puzzle).is_some(),
+                    examples,
+                    solved: [1, 2].map(|part| is_solved(&answers, &submissions, day, part)),
+                    verified,
+                    benchmark: timings
+                        .data
+                        .iter()
+                        .find(|t| t.day == day && t.total_nanos > 0_f64)
+                        .map(|t| Duration::from_secs_f64(t.total_nanos / 1_000_000_000_f64)),
+                }
+            })
+            .collect();
+
+        Ok(Status { year, days })
+    }
+
+    pub fn stars(&self) -> usize {
+        self.days.iter().map(DayStatus::stars).sum()
+    }
+
+    pub fn total_benchmark(&self) -> Duration {
+        self.days.iter().filter_map(|day| day.benchmark).sum()
+    }
+
+    fn count(&self, f: impl Fn(&DayStatus) -> Option<bool>) -> (usize, usize) {
+        let known: Vec<bool> = self.days.iter().filter_map(f).collect();
+        (known.iter().filter(|x| **x).count(), known.len())
+    }
+}
+
+impl Display for Status {
+    /// The days as a calendar of five weeks, followed by the totals and a legend.
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for week in self.days.chunks(5) {
+            let cells: Vec<String> = week.iter().map(DayStatus::cell).collect();
+            writeln!(f, "{}", cells.join("   "))?;
+        }
+
+        let scaffolded = self.days.iter().filter(|day| day.scaffolded).count();
+        let (examples_passing, examples) = self.count(|day| day.examples);
+        let (verified, recorded) = self.count(|day| day.verified);
+
+        writeln!(f)?;
+        writeln!(f, "Scaffolded: {scaffolded}/{}", self.days.len())?;
+        writeln!(f, "Examples passing: {examples_passing}/{examples}")?;
+        writeln!(f, "Stars: {}/{}", self.stars(), self.days.len() * 2)?;
+        writeln!(f, "Verified: {verified}/{recorded}")?;
+        writeln!(f, "Total benchmark: {:.2?}", self.total_benchmark())?;
+        writeln!(f)?;
+        write!(
+            f,
+            "S scaffolded · E examples pass · 1, 2 parts solved · V answers verified · ✖ failing"
+        )
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+fn optional_bool(value: Option<bool>) -> JsonValue {
+    value.map_or(JsonValue::Null, JsonValue::Boolean)
+}
+
+#[allow(clippy::cast_precision_loss)]
+fn nanos(duration: Duration) -> JsonValue {
+    JsonValue::Number(duration.as_nanos() as f64)
+}
+
+impl From<&DayStatus> for JsonValue {
+    fn from(value: &DayStatus) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert(
+            "day".into(),
+            JsonValue::Number(f64::from(value.day.into_inner())),
+        );
+        map.insert("scaffolded".into(), JsonValue::Boolean(value.scaffolded));
+        map.insert("examples".into(), optional_bool(value.examples));
+        map.insert("part_1".into(), JsonValue::Boolean(value.solved[0]));
+        map.insert("part_2".into(), JsonValue::Boolean(value.solved[1]));
+        map.insert("verified".into(), optional_bool(value.verified));
+        map.insert(
+            "benchmark_nanos".into(),
+            value.benchmark.map_or(JsonValue::Null, nanos),
+        );
+
+        JsonValue::Object(map)
+    }
+}
+
+impl From<&Status> for JsonValue {
+    #[allow(clippy::cast_precision_loss)]
+    fn from(value: &Status) -> Self {
+        let count = |n: usize| JsonValue::Number(n as f64);
+
+        let mut totals: HashMap<String, JsonValue> = HashMap::new();
+        totals.insert(
+            "scaffolded".into(),
+            count(value.days.iter().filter(|day| day.scaffolded).count()),
+        );
+        totals.insert(
+            "examples_passing".into(),
+            count(value.count(|day| day.examples).0),
+        );
+        totals.insert("stars".into(), count(value.stars()));
+        totals.insert("verified".into(), count(value.count(|day| day.verified).0));
+        totals.insert("benchmark_nanos".into(), nanos(value.total_benchmark()));
+
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+        map.insert(
+            "year".into(),
+            JsonValue::Number(f64::from(value.year.into_inner())),
+        );
+        map.insert(
+            "days".into(),
+            JsonValue::Array(value.days.iter().map(JsonValue::from).collect()),
+        );
+        map.insert("totals".into(), JsonValue::Object(totals));
+
+        JsonValue::Object(map)
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::time::Duration;
+
+    use tinyjson::JsonValue;
+
+    use super::{is_solved, DayStatus, Status};
+    use crate::{
+        day,
+        template::{
+            all_days,
+            answers::Answers,
+            submissions::{Reply, Submission, Submissions},
+            Year,
+        },
+    };
+
+    fn status() -> Status {
+        let mut days: Vec<DayStatus> = all_days()
+            .map(|day| DayStatus {
+                day,
+                scaffolded: false,
+                examples: None,
+                solved: [false, false],
+                verified: None,
+                benchmark: None,
+            })
+            .collect();
+
+        days[0] = DayStatus {
+            day: day!(1),
+            scaffolded: true,
+            examples: Some(true),
+            solved: [true, true],
+            verified: Some(true),
+            benchmark: Some(Duration::from_micros(1200)),
+        };
+        days[1] = DayStatus {
+            day: day!(2),
+            scaffolded: true,
+            examples: Some(false),
+            solved: [true, false],
+            verified: Some(false),
+            benchmark: None,
+        };
+
+        Status {
+            year: Year::home(),
+            days,
+        }
+    }
+
+    #[test]
+    fn renders_calendar() {
+        let rendered = status().to_string();
+        let lines: Vec<&str> = rendered.lines().collect();
+
+        assert!(lines[0].starts_with("01 SE12V    1.2ms   02 S✖1·✖        -   03 ····· "));
+        assert!(lines[4].starts_with("21 ·····        -"));
+        assert!(rendered.contains("Examples passing: 1/2\n"));
+        assert!(rendered.contains("Stars: 3/50\n"));
+        assert!(rendered.contains("Verified: 1/2\n"));
+    }
+
+    #[test]
+    fn serializes_to_json() {
+        let json = JsonValue::from(&status());
+        let stringified = json.stringify().unwrap();
+
+        let days: &Vec<JsonValue> = json["days"].get().unwrap();
+        assert_eq!(days.len(), 25);
+        assert_eq!(days[1]["examples"], JsonValue::Boolean(false));
+        assert_eq!(days[2]["verified"], JsonValue::Null);
+        assert_eq!(json["totals"]["stars"], JsonValue::Number(3.0));
+        assert!(stringified.contains("\"benchmark_nanos\":1200000"));
+    }
+
+    #[test]
+    fn counts_submissions_and_answers_as_solved() {
+        let mut answers = Answers::default();
+        answers.set(day!(1), None, 1, "42");
+        answers.set(day!(2), Some("alice"), 1, "42");
+
+        let submissions = Submissions {
+            data: vec![
+                Submission {
+                    day: day!(1),
+                    part: 2,
+                    answer: "1".into(),
+                    reply: Reply::TooLow,
+                },
+                Submission {
+                    day: day!(3),
+                    part: 2,
+                    answer: "2".into(),
+                    reply: Reply::Correct,
+                },
+            ],
+        };
+
+        assert!(is_solved(&answers, &submissions, day!(1), 1));
+        assert!(!is_solved(&answers, &submissions, day!(1), 2));
+        assert!(!is_solved(&answers, &submissions, day!(2), 1));
+        assert!(is_solved(&answers, &submissions, day!(3), 2));
+    }
+}
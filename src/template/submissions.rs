@@ -0,0 +1,399 @@
+use std::{
+    collections::HashMap,
+    fmt::Display,
+    fs,
+    io::{Error, ErrorKind},
+    path::{Path, PathBuf},
+    str::FromStr,
+};
+use tinyjson::JsonValue;
+
+use crate::template::{
+    aoc_client::{AnswerHint, SubmitOutcome},
+    Day, Year,
+};
+
+static SUBMISSIONS_FILE_NAME: &str = "submissions.json";
+
+/// `data/submissions.json` for the home year, `data/<year>/submissions.json` for any other year.
+fn file_path(year: Year) -> PathBuf {
+    year.data_dir("./data").join(SUBMISSIONS_FILE_NAME)
+}
+
+/// What the site replied to a submission, as stored in the history.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Reply {
+    Correct,
+    TooHigh,
+    TooLow,
+    Incorrect,
+    Wait,
+    WrongLevel,
+    Unknown,
+}
+
+impl Reply {
+    fn as_str(self) -> &'static str {
+        match self {
+            Reply::Correct => "correct",
+            Reply::TooHigh => "too_high",
+            Reply::TooLow => "too_low",
+            Reply::Incorrect => "incorrect",
+            Reply::Wait => "wait",
+            Reply::WrongLevel => "wrong_level",
+            Reply::Unknown => "unknown",
+        }
+    }
+
+    /// Whether the site judged the answer to be wrong.
+    pub fn is_wrong(self) -> bool {
+        matches!(self, Reply::TooHigh | Reply::TooLow | Reply::Incorrect)
+    }
+}
+
+impl From<&SubmitOutcome> for Reply {
+    fn from(value: &SubmitOutcome) -> Self {
+        match value {
+            SubmitOutcome::Correct => Reply::Correct,
+            SubmitOutcome::Incorrect {
+                hint: Some(AnswerHint::TooHigh),
+            } => Reply::TooHigh,
+            SubmitOutcome::Incorrect {
+                hint: Some(AnswerHint::TooLow),
+            } => Reply::TooLow,
+            SubmitOutcome::Incorrect { hint: None } => Reply::Incorrect,
+            SubmitOutcome::Wait { .. } => Reply::Wait,
+            SubmitOutcome::WrongLevel => Reply::WrongLevel,
+            SubmitOutcome::Unknown(_) => Reply::Unknown,
+        }
+    }
+}
+
+impl FromStr for Reply {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        [
+            Reply::Correct,
+            Reply::TooHigh,
+            Reply::TooLow,
+            Reply::Incorrect,
+            Reply::Wait,
+            Reply::WrongLevel,
+            Reply::Unknown,
+        ]
+        .into_iter()
+        .find(|reply| reply.as_str() == s)
+        .ok_or_else(|| format!("unknown reply `{s}`."))
+    }
+}
+
+/// A single answer that was sent to the site.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Submission {
+    pub day: Day,
+    pub part: u8,
+    pub answer: String,
+    pub reply: Reply,
+}
+
+/// Why an answer is not worth submitting, judging by earlier replies.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Refusal {
+    AlreadySolved { answer: String },
+    KnownWrong,
+    TooHigh { bound: i128 },
+    TooLow { bound: i128 },
+}
+
+impl Display for Refusal {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Refusal::AlreadySolved { answer } => {
+                write!(f, "this part was already solved with {answer}")
+            }
+            Refusal::KnownWrong => write!(f, "this answer was already submitted and is wrong"),
+            Refusal::TooHigh { bound } => write!(f, "{bound} was already too high"),
+            Refusal::TooLow { bound } => write!(f, "{bound} was already too low"),
+        }
+    }
+}
+
+/// The history of submissions, oldest first.
+/// Can be serialized from / to JSON.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Submissions {
+    pub data: Vec<Submission>,
+}
+
+impl Submissions {
+    /// Dehydrate the history to a JSON file.
+    pub fn store_file(&self, year: Year) -> Result<(), Error> {
+        let path = file_path(year);
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let json = JsonValue::from(self.clone());
+        let mut file = fs::File::create(path)?;
+        json.format_to(&mut file)
+    }
+
+    /// Rehydrate the history from a JSON file. If not present, returns an empty history.
+    /// A file that can't be read or parsed is an error, so it is never replaced by an empty history.
+    pub fn read_from_file(year: Year) -> Result<Self, String> {
+        Self::read_from_path(&file_path(year))
+    }
+
+    fn read_from_path(path: &Path) -> Result<Self, String> {
+        match fs::read_to_string(path) {
+            Ok(json) => Submissions::try_from(json),
+            Err(e) if e.kind() == ErrorKind::NotFound => Ok(Self::default()),
+            Err(e) => Err(e.to_string()),
+        }
+        .map_err(|e| format!("Could not read \"{}\": {e}", path.display()))
+    }
+
+    pub fn record(&mut self, day: Day, part: u8, answer: &str, outcome: &SubmitOutcome) {
+        self.data.push(Submission {
+            day,
+            part,
+            answer: answer.into(),
+            reply: outcome.into(),
+        });
+    }
+
+    /// Check an answer against earlier replies for the same part before submitting it.
+    /// Bounds from "too high" / "too low" replies only apply to numeric answers.
+    pub fn check(&self, day: Day, part: u8, answer: &str) -> Result<(), Refusal> {
+        let earlier = || {
+            self.data
+                .iter()
+                .filter(move |s| s.day == day && s.part == part)
+        };
+
+        if let Some(solved) = earlier().find(|s| s.reply == Reply::Correct) {
+            return Err(Refusal::AlreadySolved {
+                answer: solved.answer.clone(),
+            });
+        }
+
+        if earlier().any(|s| s.reply.is_wrong() && s.answer == answer) {
+            return Err(Refusal::KnownWrong);
+        }
+
+        let Ok(value) = answer.parse::<i128>() else {
+            return Ok(());
+        };
+
+        let bound = |reply: Reply| {
+            earlier()
+                .filter(move |s| s.reply == reply)
+                .filter_map(|s| s.answer.parse::<i128>().ok())
+        };
+
+        if let Some(bound) = bound(Reply::TooHigh).min().filter(|b| value >= *b) {
+            return Err(Refusal::TooHigh { bound });
+        }
+
+        if let Some(bound) = bound(Reply::TooLow).max().filter(|b| value <= *b) {
+            return Err(Refusal::TooLow { bound });
+        }
+
+        Ok(())
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<Submissions> for JsonValue {
+    fn from(value: Submissions) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert(
+            "data".into(),
+            JsonValue::Array(value.data.iter().map(JsonValue::from).collect()),
+        );
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<String> for Submissions {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        let json = JsonValue::from_str(&value).or(Err("not valid JSON file."))?;
+
+        let json_data = json
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("expected JSON document to be an object.")?
+            .get("data")
+            .ok_or("expected JSON document to have key `data`.")?
+            .get::<Vec<JsonValue>>()
+            .ok_or("expected `json.data` to be an array.")?;
+
+        Ok(Submissions {
+            data: json_data
+                .iter()
+                .map(Submission::try_from)
+                .collect::<Result<_, _>>()?,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<&Submission> for JsonValue {
+    fn from(value: &Submission) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert("day".into(), JsonValue::String(value.day.to_string()));
+        map.insert("part".into(), JsonValue::Number(f64::from(value.part)));
+        map.insert("answer".into(), JsonValue::String(value.answer.clone()));
+        map.insert(
+            "reply".into(),
+            JsonValue::String(value.reply.as_str().into()),
+        );
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for Submission {
+    type Error = String;
+
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected submission to be a JSON object.")?;
+
+        let day = json
+            .get("day")
+            .and_then(|v| v.get::<String>())
+            .and_then(|day| Day::from_str(day).ok())
+            .ok_or("Expected submission.day to be a Day struct.")?;
+
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        let part = json
+            .get("part")
+            .and_then(|v| v.get::<f64>())
+            .map(|part| *part as u8)
+            .ok_or("Expected submission.part to be a number.")?;
+
+        let answer = json
+            .get("answer")
+            .and_then(|v| v.get::<String>())
+            .ok_or("Expected submission.answer to be a string.")?;
+
+        let reply = json
+            .get("reply")
+            .and_then(|v| v.get::<String>())
+            .ok_or("Expected submission.reply to be a string.")?
+            .parse()?;
+
+        Ok(Submission {
+            day,
+            part,
+            answer: answer.clone(),
+            reply,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::{env, fs, process};
+
+    use super::{Refusal, Reply, Submissions};
+    use crate::{
+        day,
+        template::aoc_client::{AnswerHint, SubmitOutcome},
+    };
+
+    fn incorrect(hint: Option<AnswerHint>) -> SubmitOutcome {
+        SubmitOutcome::Incorrect { hint }
+    }
+
+    #[test]
+    fn round_trips_json() {
+        let mut submissions = Submissions::default();
+        submissions.record(day!(1), 1, "12", &incorrect(Some(AnswerHint::TooLow)));
+        submissions.record(day!(1), 1, "42", &SubmitOutcome::Correct);
+        let json = tinyjson::JsonValue::from(submissions.clone())
+            .stringify()
+            .unwrap();
+        let parsed = Submissions::try_from(json).unwrap();
+        assert_eq!(parsed, submissions);
+        assert_eq!(parsed.data[0].reply, Reply::TooLow);
+    }
+
+    #[test]
+    fn rejects_corrupt_files() {
+        let dir = env::temp_dir().join(format!("aoc-submissions-{}", process::id()));
+        fs::create_dir_all(&dir).unwrap();
+
+        let missing = dir.join("missing.json");
+        assert_eq!(
+            Submissions::read_from_path(&missing),
+            Ok(Submissions::default())
+        );
+
+        let corrupt = dir.join("corrupt.json");
+        fs::write(&corrupt, "{\"data\": [").unwrap();
+        assert!(Submissions::read_from_path(&corrupt).is_err());
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn refuses_known_wrong_answers() {
+        let mut submissions = Submissions::default();
+        submissions.record(day!(1), 1, "abc", &incorrect(None));
+        assert_eq!(
+            submissions.check(day!(1), 1, "abc"),
+            Err(Refusal::KnownWrong)
+        );
+        assert_eq!(submissions.check(day!(1), 1, "abd"), Ok(()));
+        assert_eq!(submissions.check(day!(1), 2, "abc"), Ok(()));
+    }
+
+    #[test]
+    fn refuses_answers_out_of_bounds() {
+        let mut submissions = Submissions::default();
+        submissions.record(day!(1), 1, "100", &incorrect(Some(AnswerHint::TooHigh)));
+        submissions.record(day!(1), 1, "80", &incorrect(Some(AnswerHint::TooHigh)));
+        submissions.record(day!(1), 1, "10", &incorrect(Some(AnswerHint::TooLow)));
+
+        assert_eq!(
+            submissions.check(day!(1), 1, "90"),
+            Err(Refusal::TooHigh { bound: 80 })
+        );
+        assert_eq!(
+            submissions.check(day!(1), 1, "-5"),
+            Err(Refusal::TooLow { bound: 10 })
+        );
+        assert_eq!(submissions.check(day!(1), 1, "50"), Ok(()));
+        assert_eq!(submissions.check(day!(2), 1, "90"), Ok(()));
+    }
+
+    #[test]
+    fn refuses_solved_parts() {
+        let mut submissions = Submissions::default();
+        submissions.record(day!(1), 1, "42", &SubmitOutcome::Correct);
+        assert_eq!(
+            submissions.check(day!(1), 1, "43"),
+            Err(Refusal::AlreadySolved {
+                answer: "42".into()
+            })
+        );
+    }
+
+    #[test]
+    fn ignores_inconclusive_replies() {
+        let mut submissions = Submissions::default();
+        submissions.record(day!(1), 1, "42", &SubmitOutcome::Wait { seconds: None });
+        assert_eq!(submissions.check(day!(1), 1, "42"), Ok(()));
+    }
+}
@@ -1,9 +1,14 @@
-use std::{collections::HashMap, fs, io::Error, str::FromStr};
+use std::{collections::HashMap, fs, io::Error, path::PathBuf, str::FromStr, time::Duration};
 use tinyjson::JsonValue;
 
-use crate::template::Day;
+use crate::template::{bench::BenchStats, Day, Year};
 
-static TIMINGS_FILE_PATH: &str = "./data/timings.json";
+static TIMINGS_FILE_NAME: &str = "timings.json";
+
+/// `data/timings.json` for the home year, `data/<year>/timings.json` for any other year.
+fn file_path(year: Year) -> PathBuf {
+    year.data_dir("./data").join(TIMINGS_FILE_NAME)
+}
 
 /// Represents benchmark times for a single day.
 #[derive(Clone, Debug)]
@@ -12,6 +17,36 @@ pub struct Timing {
     pub part_1: Option<String>,
     pub part_2: Option<String>,
     pub total_nanos: f64,
+    pub part_1_stats: Option<BenchStats>,
+    pub part_2_stats: Option<BenchStats>,
+}
+
+impl Timing {
+    /// The benched duration of a part. This is the median sample if stats were recorded,
+    /// otherwise it is parsed from the stored timing string.
+    pub fn part_duration(&self, part: u8) -> Option<Duration> {
+        let (timing_str, stats) = match part {
+            1 => (&self.part_1, &self.part_1_stats),
+            2 => (&self.part_2, &self.part_2_stats),
+            _ => return None,
+        };
+
+        stats
+            .as_ref()
+            .map(|stats| stats.median)
+            .or_else(|| timing_str.as_deref().and_then(parse_duration))
+    }
+}
+
+/// Parse a duration formatted with `{:.1?}`, e.g. `74.1ns` or `1.2s`.
+/// For possible formats, see: https://github.com/rust-lang/rust/blob/1.64.0/library/core/src/time.rs#L1176-L1200
+fn parse_duration(s: &str) -> Option<Duration> {
+    let (value, nanos_per_unit) = [("ns", 1_f64), ("µs", 1e3), ("ms", 1e6), ("s", 1e9)]
+        .into_iter()
+        .find_map(|(suffix, scale)| Some((s.strip_suffix(suffix)?, scale)))?;
+
+    let nanos = value.trim().parse::<f64>().ok()? * nanos_per_unit;
+    Duration::try_from_secs_f64(nanos / 1e9).ok()
 }
 
 /// Represents benchmark times for a set of days.
@@ -23,15 +58,19 @@ pub struct Timings {
 
 impl Timings {
     /// Dehydrate timings to a JSON file.
-    pub fn store_file(&self) -> Result<(), Error> {
+    pub fn store_file(&self, year: Year) -> Result<(), Error> {
+        let path = file_path(year);
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
         let json = JsonValue::from(self.clone());
-        let mut file = fs::File::create(TIMINGS_FILE_PATH)?;
+        let mut file = fs::File::create(path)?;
         json.format_to(&mut file)
     }
 
     /// Rehydrate timings from a JSON file. If not present, returns empty timings.
-    pub fn read_from_file() -> Self {
-        fs::read_to_string(TIMINGS_FILE_PATH)
+    pub fn read_from_file(year: Year) -> Self {
+        fs::read_to_string(file_path(year))
             .map_err(|x| x.to_string())
             .and_then(Timings::try_from)
             .unwrap_or_default()
@@ -51,7 +90,7 @@ impl Timings {
             }
         }
 
-        data.sort_unstable_by(|a, b| a.day.cmp(&b.day));
+        data.sort_unstable_by_key(|a| a.day);
         Timings { data }
     }
 
@@ -133,6 +172,19 @@ impl From<&Timing> for JsonValue {
             },
         );
 
+        for (key, stats) in [
+            ("part_1_stats", &value.part_1_stats),
+            ("part_2_stats", &value.part_2_stats),
+        ] {
+            map.insert(
+                key.into(),
+                match stats {
+                    Some(stats) => JsonValue::from(stats),
+                    None => JsonValue::Null,
+                },
+            );
+        }
+
         JsonValue::Object(map)
     }
 }
@@ -166,11 +218,19 @@ impl TryFrom<&JsonValue> for Timing {
             .and_then(|v| v.get::<f64>().copied())
             .ok_or("Expected timing.total_nanos to be a number.")?;
 
+        // stats are optional, so timings stored before they were recorded still load.
+        let stats = |key: &str| match json.get(key) {
+            Some(v) if !v.is_null() => BenchStats::try_from(v).map(Some),
+            _ => Ok(None),
+        };
+
         Ok(Timing {
             day,
             part_1: part_1.cloned(),
             part_2: part_2.cloned(),
             total_nanos,
+            part_1_stats: stats("part_1_stats")?,
+            part_2_stats: stats("part_2_stats")?,
         })
     }
 }
@@ -191,18 +251,24 @@ mod tests {
                     part_1: Some("10ms".into()),
                     part_2: Some("20ms".into()),
                     total_nanos: 3e+10,
+                    part_1_stats: None,
+                    part_2_stats: None,
                 },
                 Timing {
                     day: day!(2),
                     part_1: Some("30ms".into()),
                     part_2: Some("40ms".into()),
                     total_nanos: 7e+10,
+                    part_1_stats: None,
+                    part_2_stats: None,
                 },
                 Timing {
                     day: day!(4),
                     part_1: Some("40ms".into()),
                     part_2: None,
                     total_nanos: 4e+10,
+                    part_1_stats: None,
+                    part_2_stats: None,
                 },
             ],
         }
@@ -223,6 +289,18 @@ mod tests {
             assert_eq!(timing.total_nanos, 1_000_000_000_f64);
         }
 
+        #[test]
+        fn handles_json_timings_with_stats() {
+            let json = r#"{ "data": [{ "day": "01", "part_1": "1ms", "part_2": null, "total_nanos": 1000000, "part_1_stats": { "samples": 10, "outliers": 1, "mean_nanos": 1100000, "min_nanos": 900000, "median_nanos": 1000000, "p95_nanos": 2000000, "max_nanos": 2000000, "std_dev_nanos": 300000 }, "part_2_stats": null }] }"#.to_string();
+            let timings = Timings::try_from(json).unwrap();
+            let timing = timings.data.first().unwrap();
+            let stats = timing.part_1_stats.as_ref().unwrap();
+            assert_eq!(stats.samples, 10);
+            assert_eq!(stats.outliers, 1);
+            assert_eq!(stats.median.as_nanos(), 1_000_000);
+            assert_eq!(timing.part_2_stats, None);
+        }
+
         #[test]
         fn handles_empty_timings() {
             let json = r#"{ "data": [] }"#.to_string();
@@ -282,10 +360,12 @@ mod tests {
                     part_1: Some("1ms".into()),
                     part_2: Some("2ms".into()),
                     total_nanos: 3_000_000_000_f64,
+                    part_1_stats: None,
+                    part_2_stats: None,
                 }],
             };
 
-            assert_eq!(timings.is_day_complete(&day!(1)), true);
+            assert_eq!(timings.is_day_complete(day!(1)), true);
         }
 
         #[test]
@@ -296,10 +376,12 @@ mod tests {
                     part_1: Some("1ms".into()),
                     part_2: None,
                     total_nanos: 1_000_000_000_f64,
+                    part_1_stats: None,
+                    part_2_stats: None,
                 }],
             };
 
-            assert_eq!(timings.is_day_complete(&day!(1)), false);
+            assert_eq!(timings.is_day_complete(day!(1)), false);
         }
 
         #[test]
@@ -310,10 +392,60 @@ mod tests {
                     part_1: None,
                     part_2: None,
                     total_nanos: 0.0,
+                    part_1_stats: None,
+                    part_2_stats: None,
                 }],
             };
 
-            assert_eq!(timings.is_day_complete(&day!(1)), false);
+            assert_eq!(timings.is_day_complete(day!(1)), false);
+        }
+    }
+
+    mod part_duration {
+        use std::time::Duration;
+
+        use crate::{
+            day,
+            template::{bench::BenchStats, timings::Timing},
+        };
+
+        fn timing(part_1: Option<&str>, part_1_stats: Option<BenchStats>) -> Timing {
+            Timing {
+                day: day!(1),
+                part_1: part_1.map(Into::into),
+                part_2: None,
+                total_nanos: 0_f64,
+                part_1_stats,
+                part_2_stats: None,
+            }
+        }
+
+        #[test]
+        fn parses_timing_strings() {
+            for (timing_str, nanos) in [
+                ("74.1ns", 74),
+                ("1.5µs", 1_500),
+                ("20.3ms", 20_300_000),
+                ("2.0s", 2_000_000_000),
+            ] {
+                let timing = timing(Some(timing_str), None);
+                assert_eq!(timing.part_duration(1), Some(Duration::from_nanos(nanos)));
+            }
+        }
+
+        #[test]
+        fn prefers_median_from_stats() {
+            let stats = BenchStats::from_samples(&[Duration::from_nanos(10); 3]);
+            let timing = timing(Some("74.1ns"), stats);
+            assert_eq!(timing.part_duration(1), Some(Duration::from_nanos(10)));
+        }
+
+        #[test]
+        fn handles_missing_parts() {
+            let timing = timing(Some("not a duration"), None);
+            assert_eq!(timing.part_duration(1), None);
+            assert_eq!(timing.part_duration(2), None);
+            assert_eq!(timing.part_duration(3), None);
         }
     }
 
@@ -334,6 +466,8 @@ mod tests {
                     part_1: None,
                     part_2: None,
                     total_nanos: 0_f64,
+                    part_1_stats: None,
+                    part_2_stats: None,
                 }],
             };
             let merged = timings.merge(&other);
@@ -354,6 +488,8 @@ mod tests {
                     part_1: None,
                     part_2: None,
                     total_nanos: 0_f64,
+                    part_1_stats: None,
+                    part_2_stats: None,
                 }],
             };
             let merged = timings.merge(&other);
@@ -365,6 +501,18 @@ mod tests {
             assert_eq!(merged.data[2].day, day!(4));
         }
 
+        #[test]
+        fn handles_json_timings_with_stats() {
+            let json = r#"{ "data": [{ "day": "01", "part_1": "1ms", "part_2": null, "total_nanos": 1000000, "part_1_stats": { "samples": 10, "outliers": 1, "mean_nanos": 1100000, "min_nanos": 900000, "median_nanos": 1000000, "p95_nanos": 2000000, "max_nanos": 2000000, "std_dev_nanos": 300000 }, "part_2_stats": null }] }"#.to_string();
+            let timings = Timings::try_from(json).unwrap();
+            let timing = timings.data.first().unwrap();
+            let stats = timing.part_1_stats.as_ref().unwrap();
+            assert_eq!(stats.samples, 10);
+            assert_eq!(stats.outliers, 1);
+            assert_eq!(stats.median.as_nanos(), 1_000_000);
+            assert_eq!(timing.part_2_stats, None);
+        }
+
         #[test]
         fn handles_empty_timings() {
             let timings = Timings::default();
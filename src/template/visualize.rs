@@ -0,0 +1,209 @@
+//! A debug channel for solutions to show what they are doing, switched on at runtime instead of by editing code.
+//!
+//! Solutions emit frames with [`frame`], which are only rendered when the binary is run with `--visualize`,
+//! printing them to stderr, or `--frames <dir>`, writing them to numbered files such as `1-0001.txt`.
+//! `--record <file>` writes all frames to a single replay file for `cargo replay`, see [`crate::template::replay`].
+//! Frames are not emitted while a part is benched.
+use std::{
+    env,
+    fmt::Display,
+    fs::{self, File},
+    io::{stderr, IsTerminal, Write},
+    path::{Path, PathBuf},
+    sync::{
+        atomic::{AtomicBool, AtomicU8, AtomicUsize, Ordering},
+        Mutex, OnceLock,
+    },
+};
+
+use crate::template::replay::ReplayFrame;
+
+/// Where frames go, read from the binary's arguments.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+struct Config {
+    print: bool,
+    frames_dir: Option<PathBuf>,
+    record: Option<PathBuf>,
+}
+
+impl Config {
+    fn from_args() -> Option<Self> {
+        let args: Vec<String> = env::args().collect();
+
+        let value = |flag: &str| {
+            args.iter()
+                .position(|x| x == flag)
+                .and_then(|index| args.get(index + 1))
+                .map(PathBuf::from)
+        };
+
+        let config = Self {
+            print: args.iter().any(|x| x == "--visualize"),
+            frames_dir: value("--frames"),
+            record: value("--record"),
+        };
+
+        (config != Self::default()).then_some(config)
+    }
+}
+
+static CONFIG: OnceLock<Option<Config>> = OnceLock::new();
+static MUTED: AtomicBool = AtomicBool::new(false);
+static PART: AtomicU8 = AtomicU8::new(0);
+static FRAME: AtomicUsize = AtomicUsize::new(0);
+/// The replay file, created by the first part of a run.
+static RECORDING: Mutex<Option<File>> = Mutex::new(None);
+
+fn config() -> Option<&'static Config> {
+    CONFIG.get_or_init(Config::from_args).as_ref()
+}
+
+/// Whether frames are rendered at all. Use it to skip collecting state that is only needed for frames.
+pub fn enabled() -> bool {
+    config().is_some() && !MUTED.load(Ordering::Relaxed)
+}
+
+/// Emit a frame, e.g. `visualize::frame("guard turns", || canvas)`.
+/// `render` is only called when the channel is [`enabled`], so it costs nothing otherwise.
+/// Titles with values can be passed as `format_args!("moved file {id}")` to avoid formatting them, too.
+pub fn frame<D: Display>(title: impl Display, render: impl FnOnce() -> D) {
+    let Some(config) = config() else {
+        return;
+    };
+    if MUTED.load(Ordering::Relaxed) {
+        return;
+    }
+
+    let number = FRAME.fetch_add(1, Ordering::Relaxed) + 1;
+    let picture = render().to_string();
+
+    if config.print {
+        if stderr().is_terminal() {
+            eprintln!("{title}\n{picture}\n");
+        } else {
+            eprintln!("{title}\n{}\n", strip_ansi(&picture));
+        }
+    }
+
+    if let Some(dir) = &config.frames_dir {
+        let path = frame_path(dir, PART.load(Ordering::Relaxed), number);
+        let written = fs::create_dir_all(dir)
+            .and_then(|()| fs::write(&path, format!("{title}\n{}\n", strip_ansi(&picture))));
+        if let Err(e) = written {
+            eprintln!("Could not write frame \"{}\": {e}", path.display());
+        }
+    }
+
+    if config.record.is_some()
+        && let Some(file) = RECORDING.lock().unwrap().as_mut()
+    {
+        let frame = ReplayFrame {
+            part: PART.load(Ordering::Relaxed),
+            number,
+            title: title.to_string(),
+            picture,
+        };
+        if let Err(e) = file.write_all(frame.to_string().as_bytes()) {
+            eprintln!("Could not record frame: {e}");
+        }
+    }
+}
+
+/// Start numbering the frames of a part from 1 again, removing its frames from an earlier run.
+/// The replay file is started over by the first part of a run.
+pub(crate) fn start_part(part: u8) {
+    PART.store(part, Ordering::Relaxed);
+    FRAME.store(0, Ordering::Relaxed);
+
+    if let Some(path) = config().and_then(|config| config.record.as_deref()) {
+        let mut recording = RECORDING.lock().unwrap();
+        if recording.is_none() {
+            let created = path
+                .parent()
+                .map_or(Ok(()), fs::create_dir_all)
+                .and_then(|()| File::create(path));
+            match created {
+                Ok(file) => *recording = Some(file),
+                Err(e) => eprintln!("Could not create replay \"{}\": {e}", path.display()),
+            }
+        }
+    }
+
+    if let Some(dir) = config().and_then(|config| config.frames_dir.as_deref())
+        && let Ok(entries) = fs::read_dir(dir)
+    {
+        for entry in entries.flatten() {
+            if is_frame_of(&entry.file_name().to_string_lossy(), part) {
+                let _ = fs::remove_file(entry.path());
+            }
+        }
+    }
+}
+
+/// Run `f` without emitting frames, e.g. while benching.
+pub(crate) fn muted<T>(f: impl FnOnce() -> T) -> T {
+    let was_muted = MUTED.swap(true, Ordering::Relaxed);
+    let result = f();
+    MUTED.store(was_muted, Ordering::Relaxed);
+    result
+}
+
+/// `<dir>/<part>-<frame>.txt`, zero-padded so the files sort in order.
+fn frame_path(dir: &Path, part: u8, number: usize) -> PathBuf {
+    dir.join(format!("{part}-{number:04}.txt"))
+}
+
+/// Whether a file name is one of [`frame_path`]'s for `part`, so other files in the folder are kept.
+fn is_frame_of(file_name: &str, part: u8) -> bool {
+    file_name
+        .strip_prefix(&format!("{part}-"))
+        .and_then(|rest| rest.strip_suffix(".txt"))
+        .is_some_and(|number| !number.is_empty() && number.bytes().all(|b| b.is_ascii_digit()))
+}
+
+/// Remove ANSI escape sequences such as colours, for files and output that is not a terminal.
+pub fn strip_ansi(text: &str) -> String {
+    let mut stripped = String::with_capacity(text.len());
+    let mut chars = text.chars();
+
+    while let Some(ch) = chars.next() {
+        if ch == '\x1b' {
+            // skip the `[`, the parameters and the final letter.
+            for ch in chars.by_ref() {
+                if ch.is_ascii_alphabetic() {
+                    break;
+                }
+            }
+        } else {
+            stripped.push(ch);
+        }
+    }
+
+    stripped
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::path::Path;
+
+    use super::{frame_path, is_frame_of, strip_ansi};
+
+    #[test]
+    fn strips_colours() {
+        assert_eq!(strip_ansi("\x1b[41m\x1b[1mA\x1b[0mB"), "AB");
+        assert_eq!(strip_ansi("plain"), "plain");
+    }
+
+    #[test]
+    fn numbers_frames_in_order() {
+        assert_eq!(
+            frame_path(Path::new("frames"), 2, 7),
+            Path::new("frames/2-0007.txt")
+        );
+        assert!(is_frame_of("2-0007.txt", 2));
+        assert!(!is_frame_of("1-0007.txt", 2));
+        assert!(!is_frame_of("2-notes.txt", 2));
+    }
+}
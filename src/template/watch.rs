@@ -0,0 +1,127 @@
+//! Polls the files a day depends on, so `cargo solve --watch` can rerun it whenever one changes.
+//! Polling keeps this free of platform-specific file notifications.
+use std::{
+    collections::BTreeMap,
+    fs,
+    path::{Path, PathBuf},
+    thread,
+    time::{Duration, SystemTime},
+};
+
+use crate::template::{inputs::Input, Puzzle};
+
+pub const POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+/// The modification time of every file in a set of files and directories.
+pub type Snapshot = BTreeMap<PathBuf, SystemTime>;
+
+/// What a day depends on: its solution, the shared modules, its examples and the input it runs on.
+pub fn watched_paths(puzzle: Puzzle, input: &Input) -> Vec<PathBuf> {
+    let mut paths = vec![
+        Path::new("src/bin").join(format!("{}.rs", puzzle.bin_name())),
+        PathBuf::from("src/lib.rs"),
+        PathBuf::from("src/shared.rs"),
+        PathBuf::from("src/shared"),
+        input.path(puzzle),
+    ];
+
+    // `NN.txt`, `NN-2.txt` and the `NN.json` manifest.
+    let examples_dir = puzzle.year.data_dir("data").join("examples");
+    if let Ok(entries) = fs::read_dir(&examples_dir) {
+        paths.extend(
+            entries
+                .filter_map(Result::ok)
+                .map(|entry| entry.path())
+                .filter(|path| {
+                    path.file_name()
+                        .and_then(|name| name.to_str())
+                        .is_some_and(|name| name.starts_with(&puzzle.day.to_string()))
+                }),
+        );
+    }
+
+    paths
+}
+
+/// Take a snapshot of files and, recursively, the files in directories. Missing paths are skipped,
+/// so creating one shows up as a change.
+pub fn snapshot(paths: &[PathBuf]) -> Snapshot {
+    let mut snapshot = Snapshot::new();
+    for path in paths {
+        add_to_snapshot(&mut snapshot, path);
+    }
+    snapshot
+}
+
+fn add_to_snapshot(snapshot: &mut Snapshot, path: &Path) {
+    let Ok(metadata) = fs::metadata(path) else {
+        return;
+    };
+
+    if metadata.is_dir() {
+        if let Ok(entries) = fs::read_dir(path) {
+            for entry in entries.filter_map(Result::ok) {
+                add_to_snapshot(snapshot, &entry.path());
+            }
+        }
+    } else if let Ok(modified) = metadata.modified() {
+        snapshot.insert(path.to_path_buf(), modified);
+    }
+}
+
+/// Call `on_change` now and after every change to the watched paths, until the process is stopped.
+/// The paths are collected again each time, so new example files are picked up.
+pub fn watch(paths: impl Fn() -> Vec<PathBuf>, mut on_change: impl FnMut()) -> ! {
+    let mut last: Option<Snapshot> = None;
+
+    loop {
+        let current = snapshot(&paths());
+        if last.as_ref() != Some(&current) {
+            on_change();
+            // changes made while `on_change` ran are picked up by the next poll.
+            last = Some(current);
+        }
+        thread::sleep(POLL_INTERVAL);
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::{env, fs, path::PathBuf, process};
+
+    use super::{snapshot, watched_paths};
+    use crate::{
+        day,
+        template::{inputs::Input, Puzzle},
+    };
+
+    #[test]
+    fn snapshots_files_and_directories() {
+        let dir = env::temp_dir().join(format!("aoc-watch-{}", process::id()));
+        fs::create_dir_all(dir.join("nested")).unwrap();
+        fs::write(dir.join("a.txt"), "a").unwrap();
+
+        let paths = vec![dir.clone(), dir.join("missing.txt")];
+        let before = snapshot(&paths);
+        assert_eq!(before.len(), 1);
+        assert_eq!(snapshot(&paths), before);
+
+        fs::write(dir.join("nested").join("b.txt"), "b").unwrap();
+        let after = snapshot(&paths);
+        assert_eq!(after.len(), 2);
+        assert_ne!(after, before);
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn watches_the_day_and_its_input() {
+        let puzzle = Puzzle::from(day!(3));
+        let paths = watched_paths(puzzle, &Input::Named("alice".into()));
+        assert!(paths.contains(&PathBuf::from("src/bin/03.rs")));
+        assert!(paths.contains(&PathBuf::from("src/shared")));
+        assert!(paths.contains(&Input::Named("alice".into()).path(puzzle)));
+    }
+}
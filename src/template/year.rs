@@ -0,0 +1,214 @@
+use std::error::Error;
+use std::fmt::Display;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+use crate::template::Day;
+
+/// The year that files without a year in their path belong to, set with `AOC_YEAR` in `.cargo/config.toml`.
+const HOME_YEAR: Year = {
+    let digits = env!("AOC_YEAR").as_bytes();
+    let mut year: u16 = 0;
+    let mut i = 0;
+    while i < digits.len() {
+        assert!(
+            digits[i].is_ascii_digit() && i < 4,
+            "`AOC_YEAR` must be a year, e.g. `2024`"
+        );
+        year = year * 10 + (digits[i] - b'0') as u16;
+        i += 1;
+    }
+    assert!(year >= 2015, "`AOC_YEAR` must be 2015 or later");
+    Year(year)
+};
+
+/// A valid year of advent (i.e. 2015 or later).
+///
+/// # Display
+/// This value displays as a four digit number.
+///
+/// ```
+/// # use advent_of_code::template::Year;
+/// let year = Year::new(2023).unwrap();
+/// assert_eq!(year.to_string(), "2023")
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Year(u16);
+
+impl Year {
+    /// Creates a [`Year`] from the provided value if it's a year of advent,
+    /// returns [`None`] otherwise.
+    pub fn new(year: u16) -> Option<Self> {
+        if !(2015..=9999).contains(&year) {
+            return None;
+        }
+        Some(Self(year))
+    }
+
+    // Not part of the public API
+    #[doc(hidden)]
+    pub const fn __new_unchecked(year: u16) -> Self {
+        Self(year)
+    }
+
+    /// The year set with `AOC_YEAR`. Its files keep the layout of a single-year repository,
+    /// e.g. `src/bin/01.rs` and `data/inputs/01.txt`.
+    pub const fn home() -> Self {
+        HOME_YEAR
+    }
+
+    pub fn is_home(self) -> bool {
+        self == HOME_YEAR
+    }
+
+    /// Converts the [`Year`] into an [`u16`].
+    pub fn into_inner(self) -> u16 {
+        self.0
+    }
+
+    /// The directory holding this year's data within `root`:
+    /// `root` itself for the home year, `root/<year>` for any other year.
+    pub fn data_dir(self, root: impl AsRef<Path>) -> PathBuf {
+        if self.is_home() {
+            root.as_ref().to_path_buf()
+        } else {
+            root.as_ref().join(self.to_string())
+        }
+    }
+}
+
+impl Display for Year {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:04}", self.0)
+    }
+}
+
+impl FromStr for Year {
+    type Err = YearFromStrError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let year = s.parse().map_err(|_| YearFromStrError)?;
+        Self::new(year).ok_or(YearFromStrError)
+    }
+}
+
+/// An error which can be returned when parsing a [`Year`].
+#[derive(Debug)]
+pub struct YearFromStrError;
+
+impl Error for YearFromStrError {}
+
+impl Display for YearFromStrError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("expecting a year of advent, 2015 or later")
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Identifies a puzzle by its year and day.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Puzzle {
+    pub year: Year,
+    pub day: Day,
+}
+
+impl Puzzle {
+    pub const fn new(year: Year, day: Day) -> Self {
+        Self { year, day }
+    }
+
+    /// The name of the solution binary: `NN` for the home year, `YYYY_NN` for any other year.
+    pub fn bin_name(self) -> String {
+        if self.year.is_home() {
+            self.day.to_string()
+        } else {
+            format!("{}_{}", self.year, self.day)
+        }
+    }
+
+    /// The path of this puzzle's file in a data folder within `root`, e.g. `data/inputs/01.txt`
+    /// for the home year and `data/2023/inputs/01.txt` for any other year.
+    pub fn data_file(self, root: impl AsRef<Path>, folder: &str, extension: &str) -> PathBuf {
+        self.year
+            .data_dir(root)
+            .join(folder)
+            .join(format!("{}.{extension}", self.day))
+    }
+}
+
+/// A day on its own refers to the puzzle of the home year.
+impl From<Day> for Puzzle {
+    fn from(day: Day) -> Self {
+        Self::new(Year::home(), day)
+    }
+}
+
+impl Display for Puzzle {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} day {}", self.year, self.day)
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Creates a [`Year`] value in a const context.
+#[macro_export]
+macro_rules! year {
+    ($year:expr) => {{
+        const _ASSERT: () = assert!(
+            $year >= 2015 && $year <= 9999,
+            concat!(
+                "invalid year `",
+                $year,
+                "`, expecting a year of advent, 2015 or later"
+            ),
+        );
+        $crate::template::Year::__new_unchecked($year)
+    }};
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::path::Path;
+
+    use super::{Puzzle, Year};
+    use crate::{day, year};
+
+    #[test]
+    fn parses_years() {
+        assert_eq!("2023".parse::<Year>().unwrap(), year!(2023));
+        assert!("2014".parse::<Year>().is_err());
+        assert!("twenty".parse::<Year>().is_err());
+    }
+
+    #[test]
+    fn keeps_home_year_paths() {
+        let puzzle = Puzzle::from(day!(1));
+        assert_eq!(puzzle.bin_name(), "01");
+        assert_eq!(
+            puzzle.data_file("data", "inputs", "txt"),
+            Path::new("data").join("inputs").join("01.txt")
+        );
+    }
+
+    #[test]
+    fn nests_other_years() {
+        let other = if Year::home() == year!(2015) {
+            year!(2016)
+        } else {
+            year!(2015)
+        };
+        let puzzle = Puzzle::new(other, day!(7));
+        assert_eq!(puzzle.bin_name(), format!("{other}_07"));
+        assert_eq!(
+            puzzle.data_file("data", "examples", "txt"),
+            Path::new("data")
+                .join(other.to_string())
+                .join("examples")
+                .join("07.txt")
+        );
+    }
+}
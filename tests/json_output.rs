@@ -0,0 +1,29 @@
+//! Runs a solution binary with `--format json` to check that stdout stays a stream of JSON lines.
+#![cfg(feature = "test_lib")]
+
+use std::process::Command;
+
+use tinyjson::JsonValue;
+
+#[test]
+fn prints_only_json_lines_to_stdout() {
+    let output = Command::new(env!("CARGO_BIN_EXE_11"))
+        .args(["--format", "json", "--examples"])
+        .args(["--input", "data/examples/11.txt"])
+        .current_dir(env!("CARGO_MANIFEST_DIR"))
+        .output()
+        .unwrap();
+
+    assert!(output.status.success());
+
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    let lines: Vec<&str> = stdout.lines().collect();
+    assert_eq!(lines.len(), 2, "expected a line per part, got:\n{stdout}");
+    for line in lines {
+        assert!(line.parse::<JsonValue>().is_ok(), "not JSON: {line}");
+    }
+
+    // the examples are still shown, on stderr.
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert!(stderr.contains("Example 11.txt"));
+}